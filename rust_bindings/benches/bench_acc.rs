@@ -3,9 +3,11 @@ use chiavdf::{
         init_accumulators, init_seed, prove_accumulator, setup, update_accumulators,
         verify_accumulators,
     },
-    c_bindings::{evaluate_and_prove, generator, power},
+    c_bindings::evaluate_and_prove,
+    form::QuadraticForm,
 };
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use num_bigint::BigUint;
 use rand::prelude::*;
 use rand_chacha::ChaCha20Rng;
 use rand_core::SeedableRng;
@@ -39,23 +41,16 @@ pub fn bench_update(c: &mut Criterion) {
             let seed = rng.next_u32().to_ne_bytes().to_vec();
             let discriminant = &setup(&seed);
             let (acc_x, acc_y) = init_accumulators(discriminant);
-            let default_el = generator(discriminant).unwrap();
-            let seed_el = rng.next_u32() as u64;
-            let x = power(discriminant, &default_el, &seed_el.to_be_bytes()).unwrap();
-            let alpha = init_seed(&[x.clone()]);
-            let (y, _pi) = evaluate_and_prove(discriminant, &x, 10).unwrap();
+            let default_el = QuadraticForm::generator(discriminant);
+            let x = default_el.pow(&BigUint::from(rng.next_u32()));
+            let alpha = init_seed(std::slice::from_ref(&x));
+            let (y, _pi) = evaluate_and_prove(discriminant, x.as_bytes(), 10).unwrap();
+            let y = QuadraticForm::from_bytes(discriminant, &y).unwrap();
 
             let mut total_duration: Duration = Duration::ZERO;
             for _ in 0..n {
                 let start = Instant::now();
-                let _ = black_box(update_accumulators(
-                    discriminant,
-                    &acc_x,
-                    &acc_y,
-                    &x,
-                    &y,
-                    &alpha,
-                ));
+                let _ = black_box(update_accumulators(&acc_x, &acc_y, &x, &y, &alpha));
                 total_duration = total_duration.saturating_add(start.elapsed());
             }
             total_duration
@@ -77,19 +72,18 @@ pub fn bench_prove(c: &mut Criterion) {
                 let seed = rng.next_u32().to_ne_bytes().to_vec();
                 let discriminant = &setup(&seed);
                 let (acc_x, acc_y) = init_accumulators(discriminant);
-                let default_el = generator(discriminant).unwrap();
-                let seed_el = rng.next_u32() as u64;
-                let x = power(discriminant, &default_el, &seed_el.to_be_bytes()).unwrap();
-                let alpha = init_seed(&[x.clone()]);
-                let (y, _pi) = evaluate_and_prove(discriminant, &x, 10).unwrap();
+                let default_el = QuadraticForm::generator(discriminant);
+                let x = default_el.pow(&BigUint::from(rng.next_u32()));
+                let alpha = init_seed(std::slice::from_ref(&x));
+                let (y, _pi) = evaluate_and_prove(discriminant, x.as_bytes(), 10).unwrap();
+                let y = QuadraticForm::from_bytes(discriminant, &y).unwrap();
                 let (accumulator_x, accumulator_y, _) =
-                    update_accumulators(discriminant, &acc_x, &acc_y, &x, &y, &alpha);
+                    update_accumulators(&acc_x, &acc_y, &x, &y, &alpha);
 
                 let mut total_duration: Duration = Duration::ZERO;
                 for _ in 0..n {
                     let start = Instant::now();
                     let _ = black_box(prove_accumulator(
-                        discriminant,
                         &accumulator_x,
                         &accumulator_y,
                         num_iterations,
@@ -116,21 +110,20 @@ pub fn bench_verify(c: &mut Criterion) {
                 let seed = rng.next_u32().to_ne_bytes().to_vec();
                 let discriminant = &setup(&seed);
                 let (acc_x, acc_y) = init_accumulators(discriminant);
-                let default_el = generator(discriminant).unwrap();
-                let seed_el = rng.next_u32() as u64;
-                let x = power(discriminant, &default_el, &seed_el.to_be_bytes()).unwrap();
-                let alpha = init_seed(&[x.clone()]);
-                let (y, _pi) = evaluate_and_prove(discriminant, &x, 10).unwrap();
+                let default_el = QuadraticForm::generator(discriminant);
+                let x = default_el.pow(&BigUint::from(rng.next_u32()));
+                let alpha = init_seed(std::slice::from_ref(&x));
+                let (y, _pi) = evaluate_and_prove(discriminant, x.as_bytes(), 10).unwrap();
+                let y = QuadraticForm::from_bytes(discriminant, &y).unwrap();
                 let (accumulator_x, accumulator_y, _) =
-                    update_accumulators(discriminant, &acc_x, &acc_y, &x, &y, &alpha);
+                    update_accumulators(&acc_x, &acc_y, &x, &y, &alpha);
                 let proof =
-                    prove_accumulator(discriminant, &accumulator_x, &accumulator_y, num_iterations);
+                    prove_accumulator(&accumulator_x, &accumulator_y, num_iterations).unwrap();
 
                 let mut total_duration: Duration = Duration::ZERO;
                 for _ in 0..n {
                     let start = Instant::now();
                     let _ = black_box(verify_accumulators(
-                        discriminant,
                         &accumulator_x,
                         &accumulator_y,
                         &proof,
@@ -1,6 +1,4 @@
-use chiavdf::{
-    c_bindings::create_discriminant, constants::DISCRIMINANT_SIZE, hash::efficient_hash,
-};
+use chiavdf::{constants::DISCRIMINANT_SIZE, discriminant::Discriminant, hash::efficient_hash};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use rand::prelude::*;
 use rand_chacha::ChaCha20Rng;
@@ -17,8 +15,7 @@ pub fn bench_efficient_hash(c: &mut Criterion) {
             // Create discriminant
             let mut seed_u32 = rng.next_u32();
             let seed = seed_u32.to_ne_bytes().to_vec();
-            let mut disc = [0; DISCRIMINANT_SIZE / 8];
-            create_discriminant(&seed, &mut disc);
+            let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
 
             // Bench hash_to_class_group
             seed_u32 = rng.next_u32();
@@ -26,7 +23,7 @@ pub fn bench_efficient_hash(c: &mut Criterion) {
             let mut total_duration: Duration = Duration::ZERO;
             for _ in 0..n {
                 let start = Instant::now();
-                let _ = black_box(efficient_hash(&seed, &disc));
+                let _ = black_box(efficient_hash(&disc, &seed));
                 total_duration = total_duration.saturating_add(start.elapsed());
             }
             total_duration
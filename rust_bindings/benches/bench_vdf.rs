@@ -1,9 +1,11 @@
 use chiavdf::{
     c_bindings::{
-        create_discriminant, evaluate_and_prove, evaluate_to_prove, generator, power, prove,
-        prove_ext, verify,
+        evaluate_and_prove, evaluate_to_prove, generator, power, prove, prove_ext, verify,
+        verify_batch,
     },
     constants::DISCRIMINANT_SIZE,
+    discriminant::Discriminant,
+    pietrzak,
 };
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use rand::prelude::*;
@@ -19,12 +21,11 @@ pub fn bench_setup(c: &mut Criterion) {
             let mut rng = ChaCha20Rng::from_os_rng();
             let seed_u32 = rng.next_u32();
             let seed = seed_u32.to_ne_bytes().to_vec();
-            let mut disc = [0; DISCRIMINANT_SIZE / 8];
 
             let mut total_duration: Duration = Duration::ZERO;
             for _ in 0..n {
                 let start = Instant::now();
-                let _ = black_box(create_discriminant(&seed, &mut disc));
+                let _ = black_box(Discriminant::from_seed(&seed, DISCRIMINANT_SIZE));
                 total_duration = total_duration.saturating_add(start.elapsed());
             }
             total_duration
@@ -44,8 +45,7 @@ pub fn bench_evalprove(c: &mut Criterion) {
             b.iter_custom(|n| {
                 let mut rng = ChaCha20Rng::from_os_rng();
                 let seed = rng.next_u32().to_ne_bytes().to_vec();
-                let mut disc = [0; DISCRIMINANT_SIZE / 8];
-                create_discriminant(&seed, &mut disc);
+                let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
 
                 let default_el = generator(&disc).unwrap();
                 let seed_el = rng.next_u32() as u64;
@@ -75,8 +75,7 @@ pub fn bench_eval(c: &mut Criterion) {
             b.iter_custom(|n| {
                 let mut rng = ChaCha20Rng::from_os_rng();
                 let seed = rng.next_u32().to_ne_bytes().to_vec();
-                let mut disc = [0; DISCRIMINANT_SIZE / 8];
-                create_discriminant(&seed, &mut disc);
+                let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
 
                 let default_el = generator(&disc).unwrap();
                 let seed_el = rng.next_u32() as u64;
@@ -106,8 +105,7 @@ pub fn bench_prove(c: &mut Criterion) {
             b.iter_custom(|n| {
                 let mut rng = ChaCha20Rng::from_os_rng();
                 let seed = rng.next_u32().to_ne_bytes().to_vec();
-                let mut disc = [0; DISCRIMINANT_SIZE / 8];
-                create_discriminant(&seed, &mut disc);
+                let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
 
                 let default_el = generator(&disc).unwrap();
                 let seed_el = rng.next_u32() as u64;
@@ -138,8 +136,7 @@ pub fn bench_prove_slow(c: &mut Criterion) {
             b.iter_custom(|n| {
                 let mut rng = ChaCha20Rng::from_os_rng();
                 let seed = rng.next_u32().to_ne_bytes().to_vec();
-                let mut disc = [0; DISCRIMINANT_SIZE / 8];
-                create_discriminant(&seed, &mut disc);
+                let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
 
                 let default_el = generator(&disc).unwrap();
                 let seed_el = rng.next_u32() as u64;
@@ -170,8 +167,7 @@ pub fn bench_verify(c: &mut Criterion) {
             b.iter_custom(|n| {
                 let mut rng = ChaCha20Rng::from_os_rng();
                 let seed = rng.next_u32().to_ne_bytes().to_vec();
-                let mut disc = [0; DISCRIMINANT_SIZE / 8];
-                create_discriminant(&seed, &mut disc);
+                let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
 
                 let default_el = generator(&disc).unwrap();
                 let seed_el = rng.next_u32() as u64;
@@ -190,16 +186,116 @@ pub fn bench_verify(c: &mut Criterion) {
     }
 }
 
+pub fn bench_verify_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("VDF");
+
+    let num_iterations = 1_000;
+    let mut rng = ChaCha20Rng::from_os_rng();
+    let seed = rng.next_u32().to_ne_bytes().to_vec();
+    let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
+    let default_el = generator(&disc).unwrap();
+    let proofs: Vec<_> = (0..100)
+        .map(|_| {
+            let seed_el = rng.next_u32() as u64;
+            let x = power(&disc, &default_el, &seed_el.to_be_bytes()).unwrap();
+            let (y, pi) = evaluate_and_prove(&disc, &x, num_iterations).unwrap();
+            (x, y, pi)
+        })
+        .collect();
+
+    for count in [10, 100] {
+        let batch: Vec<_> = proofs[..count]
+            .iter()
+            .map(|(x, y, pi)| (&x[..], &y[..], &pi[..], num_iterations))
+            .collect();
+
+        let mut name: String = "Verify batch - ".to_string();
+        name.push_str(count.to_string().as_str());
+        group.bench_function(name, |b| b.iter(|| black_box(verify_batch(&disc, &batch))));
+
+        let mut name: String = "Verify one by one - ".to_string();
+        name.push_str(count.to_string().as_str());
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for (x, y, pi, num_iterations) in &batch {
+                    let _ = black_box(verify(&disc, x, y, pi, *num_iterations));
+                }
+            })
+        });
+    }
+}
+
+pub fn bench_pietrzak(c: &mut Criterion) {
+    let mut group = c.benchmark_group("VDF");
+
+    let iterations = [1_000, 10_000];
+
+    for num_iterations in iterations {
+        let mut name: String = "Prove Pietrzak (inter.) - ".to_string();
+        name.push_str(num_iterations.to_string().as_str());
+        group.bench_function(name, |b| {
+            b.iter_custom(|n| {
+                let mut rng = ChaCha20Rng::from_os_rng();
+                let seed = rng.next_u32().to_ne_bytes().to_vec();
+                let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
+
+                let default_el = generator(&disc).unwrap();
+                let seed_el = rng.next_u32() as u64;
+                let x = power(&disc, &default_el, &seed_el.to_be_bytes()).unwrap();
+                let (y, intermediates) = evaluate_to_prove(&disc, &x, num_iterations).unwrap();
+
+                let mut total_duration: Duration = Duration::ZERO;
+                for _ in 0..n {
+                    let start = Instant::now();
+                    let _ = black_box(pietrzak::prove(
+                        &disc,
+                        &x,
+                        &y,
+                        &intermediates,
+                        num_iterations,
+                    ));
+                    total_duration = total_duration.saturating_add(start.elapsed());
+                }
+                total_duration
+            })
+        });
+
+        let mut name: String = "Verify Pietrzak - ".to_string();
+        name.push_str(num_iterations.to_string().as_str());
+        group.bench_function(name, |b| {
+            b.iter_custom(|n| {
+                let mut rng = ChaCha20Rng::from_os_rng();
+                let seed = rng.next_u32().to_ne_bytes().to_vec();
+                let disc = Discriminant::from_seed(&seed, DISCRIMINANT_SIZE).unwrap();
+
+                let default_el = generator(&disc).unwrap();
+                let seed_el = rng.next_u32() as u64;
+                let x = power(&disc, &default_el, &seed_el.to_be_bytes()).unwrap();
+                let (y, intermediates) = evaluate_to_prove(&disc, &x, num_iterations).unwrap();
+                let pi = pietrzak::prove(&disc, &x, &y, &intermediates, num_iterations).unwrap();
+
+                let mut total_duration: Duration = Duration::ZERO;
+                for _ in 0..n {
+                    let start = Instant::now();
+                    let _ = black_box(pietrzak::verify(&disc, &x, &y, &pi, num_iterations));
+                    total_duration = total_duration.saturating_add(start.elapsed());
+                }
+                total_duration
+            })
+        });
+    }
+}
+
 mod criterion_group {
     #![allow(missing_docs)]
     use super::{
-        bench_eval, bench_evalprove, bench_prove, bench_prove_slow, bench_setup, bench_verify,
-        criterion_group, Criterion,
+        bench_eval, bench_evalprove, bench_pietrzak, bench_prove, bench_prove_slow, bench_setup,
+        bench_verify, bench_verify_batch, criterion_group, Criterion,
     };
 
     criterion_group!(name = all;
                      config = Criterion::default();
-                     targets = bench_setup, bench_evalprove, bench_eval, bench_prove, bench_prove_slow, bench_verify
+                     targets = bench_setup, bench_evalprove, bench_eval, bench_prove, bench_prove_slow, bench_verify, bench_verify_batch, bench_pietrzak
     );
 }
 
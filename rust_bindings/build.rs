@@ -1,8 +1,16 @@
+#[cfg(feature = "cpp")]
 use std::env;
+#[cfg(feature = "cpp")]
 use std::path::PathBuf;
 
+#[cfg(feature = "cpp")]
 use cmake::Config;
 
+// The pure-Rust code needs neither the C++ library nor its bindings
+#[cfg(not(feature = "cpp"))]
+fn main() {}
+
+#[cfg(feature = "cpp")]
 fn main() {
     println!("cargo:rerun-if-changed=wrapper.h");
     println!("cargo:rerun-if-changed=../src/c_bindings/c_wrapper.h");
@@ -19,12 +27,16 @@ fn main() {
             .to_path_buf();
     }
 
-    let dst = Config::new(src_dir.as_path())
+    let mut config = Config::new(src_dir.as_path());
+    config
         .build_target("chiavdfc_static")
         .define("BUILD_CHIAVDFC", "ON")
         .env("BUILD_VDF_CLIENT", "N")
-        .define("BUILD_PYTHON", "OFF")
-        .build();
+        .define("BUILD_PYTHON", "OFF");
+    if cfg!(feature = "timelord") {
+        config.define("BUILD_CHIAVDFC_TIMELORD", "ON");
+    }
+    let dst = config.build();
 
     println!("cargo:rustc-link-lib=static=chiavdfc");
 
@@ -61,22 +73,44 @@ fn main() {
             src_dir.join("c_bindings").to_str().unwrap()
         ))
         .clang_arg("-std=c++14")
+        .clang_args(cfg!(feature = "timelord").then_some("-DCHIAVDFC_TIMELORD"))
         .allowlist_function("verify_n_wesolowski_wrapper")
+        .allowlist_function("create_discriminant_and_verify_n_wesolowski_wrapper")
+        .allowlist_function("verify_n_wesolowski_with_b_wrapper")
+        .allowlist_function("get_b_from_n_wesolowski_wrapper")
         .allowlist_function("create_discriminant_wrapper")
         .allowlist_function("prove_wrapper")
         .allowlist_function("evaluate_to_prove_wrapper")
+        .allowlist_function("resume_evaluation_wrapper")
+        .allowlist_function("evaluate_wrapper")
         .allowlist_function("prove_only_wrapper")
         .allowlist_function("prove_int_only_wrapper")
+        .allowlist_function("pietrzak_prove_wrapper")
+        .allowlist_function("pietrzak_verify_wrapper")
+        .allowlist_function("prove_n_wesolowski_wrapper")
+        .allowlist_function("prover_session_start")
+        .allowlist_function("prover_session_iterations")
+        .allowlist_function("prover_session_prove")
+        .allowlist_function("prover_session_stop")
+        .allowlist_function("prover_session_free")
         .allowlist_function("verify_wrapper")
+        .allowlist_function("verify_batch_wrapper")
         .allowlist_function("free")
         .allowlist_function("delete_byte_array")
         .allowlist_function("from_ab")
+        .allowlist_function("from_ab_reduced")
+        .allowlist_function("decompress_wrapper")
         .allowlist_function("generator_wrapper")
         .allowlist_function("identity_wrapper")
         .allowlist_function("hash_int_wrapper")
         .allowlist_function("hash_prime_wrapper")
+        .allowlist_function("challenge_prime_wrapper")
+        .allowlist_function("challenge_int_wrapper")
         .allowlist_function("power_wrapper")
         .allowlist_function("multiply_wrapper")
+        .allowlist_function("chiavdf_last_error_kind")
+        .allowlist_function("chiavdf_last_error_message")
+        .allowlist_var("CHIAVDF_ERROR_.*")
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
         .generate()
         .expect("Unable to generate bindings");
@@ -1,6 +1,7 @@
 #![no_main]
 
-use chiavdf::c_bindings::{create_discriminant, evaluate_and_prove};
+use chiavdf::c_bindings::evaluate_and_prove;
+use chiavdf::discriminant::Discriminant;
 use libfuzzer_sys::{arbitrary::Unstructured, fuzz_target};
 
 pub const DISCRIMINANT_SIZE: usize = 4_096;
@@ -10,12 +11,11 @@ fuzz_target!(|data: &[u8]| {
     let mut unstructured = Unstructured::new(data);
 
     let genesis_challenge: [u8; 32] = unstructured.arbitrary().unwrap();
-    let mut disc = [0; DISCRIMINANT_SIZE / 8];
-    if !create_discriminant(&genesis_challenge, &mut disc) {
+    let Some(disc) = Discriminant::from_seed(&genesis_challenge, DISCRIMINANT_SIZE) else {
         return;
     };
 
     let element: [u8; FORM_SIZE] = unstructured.arbitrary().unwrap();
     let iters: u8 = unstructured.arbitrary().unwrap();
-    evaluate_and_prove(&disc, &element, iters as u64);
+    let _ = evaluate_and_prove(&disc, &element, iters as u64);
 });
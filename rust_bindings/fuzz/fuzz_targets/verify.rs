@@ -1,6 +1,7 @@
 #![no_main]
 
-use chiavdf::c_bindings::{create_discriminant, n_prove, verify_n_wesolowski};
+use chiavdf::c_bindings::{n_prove, verify_n_wesolowski};
+use chiavdf::discriminant::Discriminant;
 use libfuzzer_sys::{arbitrary::Unstructured, fuzz_target};
 
 pub const DISCRIMINANT_SIZE: usize = 4_096;
@@ -9,8 +10,7 @@ pub const FORM_SIZE: usize = 388;
 fuzz_target!(|data: &[u8]| {
     let mut unstructured = Unstructured::new(data);
     let genesis_challenge: [u8; 32] = unstructured.arbitrary().unwrap();
-    let mut disc = [0; DISCRIMINANT_SIZE / 8];
-    if !create_discriminant(&genesis_challenge, &mut disc) {
+    let Some(disc) = Discriminant::from_seed(&genesis_challenge, DISCRIMINANT_SIZE) else {
         return;
     };
 
@@ -18,5 +18,5 @@ fuzz_target!(|data: &[u8]| {
     default_el[0] = 0x08;
     let proof = n_prove(&disc, &default_el, 231).unwrap();
     let valid = verify_n_wesolowski(&disc, &default_el, &proof, 231, 0);
-    assert!(valid);
+    assert_eq!(valid, Ok(true));
 });
@@ -1,79 +1,244 @@
+use num_bigint::BigUint;
 use sha2::{Digest, Sha256};
 
 use super::c_bindings;
 use super::constants::DISCRIMINANT_SIZE;
+use super::create_discriminant::hash_int;
+use super::discriminant::Discriminant;
+use super::error::{ChiavdfError, ChiavdfResult};
+use super::form::QuadraticForm;
 
-pub fn setup(seed: &[u8]) -> Vec<u8> {
-    let mut disc = [0; DISCRIMINANT_SIZE / 8];
-    assert!(c_bindings::create_discriminant(seed, &mut disc));
-    disc.to_vec()
+pub fn setup(seed: &[u8]) -> Discriminant {
+    Discriminant::from_seed(seed, DISCRIMINANT_SIZE).unwrap()
 }
 
-pub fn init_accumulators(discriminant: &[u8]) -> (Vec<u8>, Vec<u8>) {
-    let acc = c_bindings::identity(discriminant).unwrap();
+pub fn init_accumulators(discriminant: &Discriminant) -> (QuadraticForm, QuadraticForm) {
+    let acc = QuadraticForm::identity(discriminant);
     (acc.clone(), acc)
 }
 
-pub fn init_seed(xs: &[Vec<u8>]) -> Vec<u8> {
+pub fn init_seed(xs: &[QuadraticForm]) -> Vec<u8> {
     let mut hasher = Sha256::new();
-    xs.iter().for_each(|xi| hasher.update(xi));
+    xs.iter().for_each(|xi| hasher.update(xi.as_bytes()));
     hasher.finalize().to_vec()
 }
 
 pub fn update_accumulators(
-    discriminant: &[u8],
-    acc_x: &[u8],
-    acc_y: &[u8],
-    x_i: &[u8],
-    y_i: &[u8],
+    acc_x: &QuadraticForm,
+    acc_y: &QuadraticForm,
+    x_i: &QuadraticForm,
+    y_i: &QuadraticForm,
     seed: &[u8],
-) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+) -> (QuadraticForm, QuadraticForm, Vec<u8>) {
     let mut hasher = Sha256::new();
     hasher.update(seed);
-    hasher.update(y_i);
+    hasher.update(y_i.as_bytes());
     let exponent_seed = &hasher.finalize();
     let mut exponent = [0u8; 128 / 8];
-    let success = c_bindings::hash_int(exponent_seed, &mut exponent);
-    assert!(success);
+    hash_int(exponent_seed, &mut exponent).expect("Hashing to an integer succeeds");
+    let exponent = BigUint::from_bytes_be(&exponent);
 
-    let x_raised = c_bindings::power(discriminant, x_i, &exponent).unwrap();
-    let updated_acc_x = c_bindings::multiply(discriminant, acc_x, &x_raised).unwrap();
+    let updated_acc_x = acc_x * &x_i.pow(&exponent);
+    let updated_acc_y = acc_y * &y_i.pow(&exponent);
 
-    let y_raised = c_bindings::power(discriminant, y_i, &exponent).unwrap();
-    let updated_acc_y = c_bindings::multiply(discriminant, acc_y, &y_raised).unwrap();
-
-    (
-        updated_acc_x.to_vec(),
-        updated_acc_y.to_vec(),
-        exponent_seed.to_vec(),
-    )
+    (updated_acc_x, updated_acc_y, exponent_seed.to_vec())
 }
 
 pub fn prove_accumulator(
-    discriminant: &[u8],
-    accumulator_x: &[u8],
-    accumulator_y: &[u8],
+    accumulator_x: &QuadraticForm,
+    accumulator_y: &QuadraticForm,
     num_iterations: u64,
-) -> Vec<u8> {
-    c_bindings::prove(discriminant, accumulator_x, accumulator_y, num_iterations).unwrap()
+) -> ChiavdfResult<Vec<u8>> {
+    c_bindings::prove(
+        accumulator_x.discriminant(),
+        accumulator_x.as_bytes(),
+        accumulator_y.as_bytes(),
+        num_iterations,
+    )
 }
 
 pub fn verify_accumulators(
-    discriminant: &[u8],
-    accumulator_x: &[u8],
-    accumulator_y: &[u8],
+    accumulator_x: &QuadraticForm,
+    accumulator_y: &QuadraticForm,
     proof: &[u8],
     num_iterations: u64,
-) -> bool {
+) -> ChiavdfResult<bool> {
     c_bindings::verify(
-        discriminant,
-        accumulator_x,
-        accumulator_y,
+        accumulator_x.discriminant(),
+        accumulator_x.as_bytes(),
+        accumulator_y.as_bytes(),
         proof,
         num_iterations,
     )
 }
 
+/// Check that each `ys[i]` is `xs[i]` squared `num_iterations` times, given the Wesolowski proof of
+/// their accumulation, e.g. [AccumulatedProof::proof]. The accumulators are recomputed from the
+/// pairs, which only costs two 128-bit exponentiations per pair, so they don't need to be trusted.
+pub fn verify_accumulated(
+    discriminant: &Discriminant,
+    xs: &[QuadraticForm],
+    ys: &[QuadraticForm],
+    proof: &[u8],
+    num_iterations: u64,
+) -> ChiavdfResult<bool> {
+    if xs.len() != ys.len() {
+        return Err(ChiavdfError::InvalidInput(
+            "There must be one output per input".to_string(),
+        ));
+    }
+    if xs
+        .iter()
+        .chain(ys)
+        .any(|form| form.discriminant() != discriminant)
+    {
+        return Err(ChiavdfError::InvalidInput(
+            "Forms must have the discriminant of the accumulator".to_string(),
+        ));
+    }
+    let (acc_x, acc_y) = init_accumulators(discriminant);
+    let (acc_x, acc_y, _) = xs.iter().zip(ys).fold(
+        (acc_x, acc_y, init_seed(xs)),
+        |(acc_x, acc_y, seed), (x_i, y_i)| update_accumulators(&acc_x, &acc_y, x_i, y_i, &seed),
+    );
+    verify_accumulators(&acc_x, &acc_y, proof, num_iterations)
+}
+
+/// The accumulated x and y of a list of VDF outputs and the Wesolowski proof that
+/// `accumulator_y` is `accumulator_x` squared T times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccumulatedProof {
+    pub accumulator_x: QuadraticForm,
+    pub accumulator_y: QuadraticForm,
+    pub proof: Vec<u8>,
+}
+
+/// Accumulates the outputs y_i = x_i^(2^T) of a list of inputs committed to when it is created,
+/// so that they can all be proven with a single proof. Each x_i must be absorbed with its y_i in
+/// the order of the list and only once, and the proof can only be made once all are absorbed.
+#[derive(Clone, Debug)]
+pub struct Accumulator {
+    discriminant: Discriminant,
+    xs: Vec<QuadraticForm>,
+    num_iterations: u64,
+    accumulator_x: QuadraticForm,
+    accumulator_y: QuadraticForm,
+    seed: Vec<u8>,
+    absorbed: usize,
+    finalized: bool,
+}
+
+impl Accumulator {
+    /// Commit to the inputs `xs`, which must all belong to the class group of `discriminant`.
+    pub fn new(
+        discriminant: &Discriminant,
+        xs: Vec<QuadraticForm>,
+        num_iterations: u64,
+    ) -> ChiavdfResult<Self> {
+        if xs.iter().any(|x_i| x_i.discriminant() != discriminant) {
+            return Err(ChiavdfError::InvalidInput(
+                "Inputs must have the discriminant of the accumulator".to_string(),
+            ));
+        }
+        let (accumulator_x, accumulator_y) = init_accumulators(discriminant);
+        let seed = init_seed(&xs);
+        Ok(Self {
+            discriminant: discriminant.clone(),
+            xs,
+            num_iterations,
+            accumulator_x,
+            accumulator_y,
+            seed,
+            absorbed: 0,
+            finalized: false,
+        })
+    }
+
+    /// The number of inputs absorbed so far.
+    pub fn absorbed(&self) -> usize {
+        self.absorbed
+    }
+
+    /// Absorb the next input `x_i` and its output `y_i`. Fails if `x_i` is not the next committed
+    /// input, e.g. if it was already absorbed.
+    pub fn absorb(&mut self, x_i: &QuadraticForm, y_i: &QuadraticForm) -> ChiavdfResult<()> {
+        match self.xs.get(self.absorbed) {
+            None => {
+                return Err(ChiavdfError::InvalidInput(
+                    "All the inputs are already absorbed".to_string(),
+                ))
+            }
+            Some(next) if next != x_i => {
+                return Err(ChiavdfError::InvalidInput(
+                    "The input is not the next committed one".to_string(),
+                ))
+            }
+            Some(_) => {}
+        }
+        if y_i.discriminant() != &self.discriminant {
+            return Err(ChiavdfError::InvalidInput(
+                "Outputs must have the discriminant of the accumulator".to_string(),
+            ));
+        }
+        let (accumulator_x, accumulator_y, seed) = update_accumulators(
+            &self.accumulator_x,
+            &self.accumulator_y,
+            x_i,
+            y_i,
+            &self.seed,
+        );
+        self.accumulator_x = accumulator_x;
+        self.accumulator_y = accumulator_y;
+        self.seed = seed;
+        self.absorbed += 1;
+        Ok(())
+    }
+
+    /// Prove the accumulated outputs. Fails if some of the committed inputs are not absorbed yet,
+    /// or if the accumulator was already finalized.
+    pub fn finalize(&mut self) -> ChiavdfResult<AccumulatedProof> {
+        if self.finalized {
+            return Err(ChiavdfError::InvalidInput(
+                "The accumulator is already finalized".to_string(),
+            ));
+        }
+        if self.absorbed < self.xs.len() {
+            return Err(ChiavdfError::InvalidInput(format!(
+                "Only {} of the {} inputs are absorbed",
+                self.absorbed,
+                self.xs.len()
+            )));
+        }
+        let proof = prove_accumulator(
+            &self.accumulator_x,
+            &self.accumulator_y,
+            self.num_iterations,
+        )?;
+        self.finalized = true;
+        Ok(AccumulatedProof {
+            accumulator_x: self.accumulator_x.clone(),
+            accumulator_y: self.accumulator_y.clone(),
+            proof,
+        })
+    }
+
+    /// Check that `proof` proves the outputs absorbed by this accumulator.
+    pub fn verify(&self, proof: &AccumulatedProof) -> ChiavdfResult<bool> {
+        if self.absorbed < self.xs.len()
+            || proof.accumulator_x != self.accumulator_x
+            || proof.accumulator_y != self.accumulator_y
+        {
+            return Ok(false);
+        }
+        verify_accumulators(
+            &proof.accumulator_x,
+            &proof.accumulator_y,
+            &proof.proof,
+            self.num_iterations,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::hash;
@@ -81,6 +246,27 @@ mod tests {
     use rand_chacha::ChaCha20Rng;
     use rand_core::{RngCore, SeedableRng};
 
+    /// Four inputs hashed into the class group of `discriminant` and their outputs after
+    /// `num_iterations` squarings.
+    fn evaluated_inputs(
+        discriminant: &Discriminant,
+        num_iterations: u64,
+    ) -> (Vec<QuadraticForm>, Vec<QuadraticForm>) {
+        let xs: Vec<QuadraticForm> = (0u32..4)
+            .map(|i| hash::efficient_hash(discriminant, &i.to_be_bytes()).unwrap())
+            .collect();
+        let ys = xs
+            .iter()
+            .map(|x_i| {
+                let (y_i, _) =
+                    c_bindings::evaluate_and_prove(discriminant, x_i.as_bytes(), num_iterations)
+                        .unwrap();
+                QuadraticForm::from_bytes(discriminant, &y_i).unwrap()
+            })
+            .collect();
+        (xs, ys)
+    }
+
     #[test]
     fn test_accumulator() {
         let num_iterations: u64 = 1_000;
@@ -91,13 +277,13 @@ mod tests {
         let (acc_x, acc_y) = init_accumulators(discriminant);
 
         // Computing all elements
-        let mut xs: Vec<Vec<u8>> = Vec::with_capacity(10);
+        let mut xs: Vec<QuadraticForm> = Vec::with_capacity(10);
         let mut rng = ChaCha20Rng::seed_from_u64(42);
         for _i in 0..10 {
             // Create new element
             let seed_i = rng.next_u32().to_be_bytes();
-            let x_i = hash::efficient_hash(&discriminant, &seed_i);
-            xs.push(x_i.clone().unwrap());
+            let x_i = hash::efficient_hash(discriminant, &seed_i);
+            xs.push(x_i.unwrap());
         }
 
         // Computing the initial seed
@@ -111,30 +297,124 @@ mod tests {
 
                 // Compute the VDF evaluation and proof
                 let (y_i, pi_i) =
-                    c_bindings::evaluate_and_prove(discriminant, x_i, num_iterations).unwrap();
+                    c_bindings::evaluate_and_prove(discriminant, x_i.as_bytes(), num_iterations)
+                        .unwrap();
 
                 assert!(c_bindings::verify(
                     discriminant,
-                    &x_i,
+                    x_i.as_bytes(),
                     &y_i,
                     &pi_i,
                     num_iterations
-                ));
+                )
+                .unwrap());
 
                 // Update the accumulators
-                let res = update_accumulators(discriminant, &xx, &yy, &x_i, &y_i, &seed);
-                res
+                let y_i = QuadraticForm::from_bytes(discriminant, &y_i).unwrap();
+                update_accumulators(&xx, &yy, x_i, &y_i, &seed)
             });
 
         // Compute final proof
-        let pi = prove_accumulator(discriminant, &accumulator_x, &accumulator_y, num_iterations);
-
-        assert!(verify_accumulators(
-            discriminant,
-            &accumulator_x,
-            &accumulator_y,
-            &pi,
-            num_iterations
+        let pi = prove_accumulator(&accumulator_x, &accumulator_y, num_iterations).unwrap();
+
+        assert!(verify_accumulators(&accumulator_x, &accumulator_y, &pi, num_iterations).unwrap());
+    }
+
+    #[test]
+    fn test_accumulator_lifecycle() {
+        let num_iterations: u64 = 1_000;
+        let discriminant = &setup(b"HelloWorld");
+        let (xs, ys) = evaluated_inputs(discriminant, num_iterations);
+
+        let mut accumulator = Accumulator::new(discriminant, xs.clone(), num_iterations).unwrap();
+        assert!(accumulator.finalize().is_err());
+
+        // Out of order
+        assert!(accumulator.absorb(&xs[1], &ys[1]).is_err());
+        accumulator.absorb(&xs[0], &ys[0]).unwrap();
+        // Twice
+        assert!(accumulator.absorb(&xs[0], &ys[0]).is_err());
+        accumulator.absorb(&xs[1], &ys[1]).unwrap();
+        assert_eq!(accumulator.absorbed(), 2);
+        assert!(matches!(
+            accumulator.finalize(),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+        accumulator.absorb(&xs[2], &ys[2]).unwrap();
+        accumulator.absorb(&xs[3], &ys[3]).unwrap();
+        assert!(accumulator.absorb(&xs[3], &ys[3]).is_err());
+
+        let proof = accumulator.finalize().unwrap();
+        assert_eq!(accumulator.verify(&proof), Ok(true));
+        // Only once
+        assert!(matches!(
+            accumulator.finalize(),
+            Err(ChiavdfError::InvalidInput(_))
         ));
+
+        // The same accumulators as folding the free functions by hand
+        let (acc_x, acc_y) = init_accumulators(discriminant);
+        let (acc_x, acc_y, _) = xs.iter().zip(&ys).fold(
+            (acc_x, acc_y, init_seed(&xs)),
+            |(acc_x, acc_y, seed), (x_i, y_i)| update_accumulators(&acc_x, &acc_y, x_i, y_i, &seed),
+        );
+        assert_eq!(proof.accumulator_x, acc_x);
+        assert_eq!(proof.accumulator_y, acc_y);
+
+        // A proof of other outputs does not verify
+        let mut other = Accumulator::new(discriminant, xs.clone(), num_iterations).unwrap();
+        for (x_i, y_i) in xs.iter().zip(&ys).take(3) {
+            other.absorb(x_i, y_i).unwrap();
+        }
+        other.absorb(&xs[3], &ys[0]).unwrap();
+        let other_proof = other.finalize().unwrap();
+        assert_eq!(other.verify(&other_proof), Ok(false));
+        assert_eq!(accumulator.verify(&other_proof), Ok(false));
+
+        let mut tampered = proof.clone();
+        tampered.proof = other_proof.proof;
+        assert_eq!(accumulator.verify(&tampered), Ok(false));
+
+        let other_discriminant = &setup(b"Other");
+        assert!(Accumulator::new(other_discriminant, xs, num_iterations).is_err());
+    }
+
+    #[test]
+    fn test_verify_accumulated() {
+        let num_iterations: u64 = 1_000;
+        let discriminant = &setup(b"HelloWorld");
+        let (xs, ys) = evaluated_inputs(discriminant, num_iterations);
+        let mut accumulator = Accumulator::new(discriminant, xs.clone(), num_iterations).unwrap();
+        for (x_i, y_i) in xs.iter().zip(&ys) {
+            accumulator.absorb(x_i, y_i).unwrap();
+        }
+        let proof = accumulator.finalize().unwrap().proof;
+
+        let verify = |xs: &[QuadraticForm], ys: &[QuadraticForm], num_iterations| {
+            verify_accumulated(discriminant, xs, ys, &proof, num_iterations)
+        };
+        assert_eq!(verify(&xs, &ys, num_iterations), Ok(true));
+        assert_eq!(verify(&xs, &ys, num_iterations + 1), Ok(false));
+
+        // A wrong output, swapped outputs or another order are caught
+        let mut wrong = ys.clone();
+        wrong[2] = xs[2].clone();
+        assert_eq!(verify(&xs, &wrong, num_iterations), Ok(false));
+        let mut swapped = ys.clone();
+        swapped.swap(0, 1);
+        assert_eq!(verify(&xs, &swapped, num_iterations), Ok(false));
+        let (mut reordered_xs, mut reordered_ys) = (xs.clone(), ys.clone());
+        reordered_xs.swap(0, 1);
+        reordered_ys.swap(0, 1);
+        assert_eq!(
+            verify(&reordered_xs, &reordered_ys, num_iterations),
+            Ok(false)
+        );
+        assert_eq!(verify(&xs[..3], &ys[..3], num_iterations), Ok(false));
+
+        assert!(verify(&xs, &ys[..3], num_iterations).is_err());
+        let other_discriminant = &setup(b"Other");
+        let other = vec![QuadraticForm::generator(other_discriminant); 4];
+        assert!(verify(&xs, &other, num_iterations).is_err());
     }
 }
@@ -0,0 +1,245 @@
+//! Futures-returning versions of the proving and verification calls of [crate::c_bindings], for
+//! async code running on Tokio. The calls run on worker threads of their own, so they hold up
+//! neither the worker threads of the runtime nor its blocking pool, which also serves `tokio::fs`
+//! and DNS lookups.
+//!
+//! Dropping a future cancels its call: a call which hasn't started yet never runs, and a running
+//! evaluation or proof stops at its next squaring and frees its worker thread.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use tokio::sync::oneshot;
+
+use super::c_bindings;
+use super::discriminant::Discriminant;
+use super::error::{ChiavdfError, ChiavdfResult};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Runs the calls of its futures on `max_jobs` worker threads, in the order they are made. Clones
+/// share the same threads, which exit once the last clone is dropped and the calls made are done.
+#[derive(Clone, Debug)]
+pub struct AsyncVdf {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl AsyncVdf {
+    /// Panics if `max_jobs` is 0 or the threads can't be spawned.
+    pub fn new(max_jobs: usize) -> Self {
+        assert!(max_jobs > 0, "At least one job must be allowed to run");
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for i in 0..max_jobs {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("chiavdf-async-{i}"))
+                .spawn(move || loop {
+                    // The lock is only held while waiting for the next job
+                    let job = receiver.lock().expect("Jobs never panic").recv();
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => break,
+                    }
+                })
+                .expect("Failed to spawn a worker thread");
+        }
+        Self { jobs }
+    }
+
+    /// See [c_bindings::evaluate_and_prove].
+    pub async fn evaluate_and_prove(
+        &self,
+        discriminant: Discriminant,
+        x_s: Vec<u8>,
+        num_iterations: u64,
+    ) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+        self.run(move |cancel| {
+            c_bindings::evaluate_and_prove_cancellable(&discriminant, &x_s, num_iterations, cancel)
+        })
+        .await
+    }
+
+    /// See [c_bindings::prove].
+    pub async fn prove(
+        &self,
+        discriminant: Discriminant,
+        x_s: Vec<u8>,
+        y_s: Vec<u8>,
+        num_iterations: u64,
+    ) -> ChiavdfResult<Vec<u8>> {
+        self.run(move |cancel| {
+            c_bindings::prove_cancellable(&discriminant, &x_s, &y_s, num_iterations, cancel)
+        })
+        .await
+    }
+
+    /// See [c_bindings::verify_n_wesolowski]. A verification is short, so it is only cancelled
+    /// if dropped before it starts.
+    pub async fn verify_n_wesolowski(
+        &self,
+        discriminant: Discriminant,
+        x_s: Vec<u8>,
+        proof: Vec<u8>,
+        num_iterations: u64,
+        recursion: u64,
+    ) -> ChiavdfResult<bool> {
+        self.run(move |_| {
+            c_bindings::verify_n_wesolowski(&discriminant, &x_s, &proof, num_iterations, recursion)
+        })
+        .await
+    }
+
+    // Run `call` on a worker thread once one is free, with a flag which is set when the returned
+    // future is dropped. A panic of `call` is resumed in the future.
+    async fn run<T: Send + 'static>(
+        &self,
+        call: impl FnOnce(&AtomicBool) -> ChiavdfResult<T> + Send + 'static,
+    ) -> ChiavdfResult<T> {
+        let cancel = CancelOnDrop(Arc::new(AtomicBool::new(false)));
+        let flag = cancel.0.clone();
+        let (sender, receiver) = oneshot::channel();
+        let job: Job = Box::new(move || {
+            if flag.load(Ordering::Relaxed) {
+                return;
+            }
+            let result = panic::catch_unwind(AssertUnwindSafe(|| call(&flag)));
+            // The future may be dropped by now
+            let _ = sender.send(result);
+        });
+        self.jobs
+            .send(job)
+            .expect("The worker threads run as long as the sender exists");
+        match receiver.await {
+            Ok(Ok(result)) => result,
+            Ok(Err(payload)) => panic::resume_unwind(payload),
+            // Only dropped unsent once cancelled
+            Err(_) => Err(ChiavdfError::Cancelled),
+        }
+    }
+}
+
+// Sets the cancellation flag of a call when the future waiting for it is dropped.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+
+    use super::*;
+    use crate::c_bindings::{evaluate, generator, prove};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_async_vdf() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let vdf = AsyncVdf::new(2);
+
+        let (y, proof) = vdf
+            .evaluate_and_prove(disc.clone(), x.clone(), 1000)
+            .await
+            .unwrap();
+        assert_eq!(y, evaluate(&disc, &x, 1000).unwrap());
+        assert_eq!(
+            vdf.prove(disc.clone(), x.clone(), y.clone(), 1000).await,
+            prove(&disc, &x, &y, 1000)
+        );
+        assert_eq!(proof, prove(&disc, &x, &y, 1000).unwrap());
+
+        let blob = [y, proof].concat();
+        assert_eq!(
+            vdf.verify_n_wesolowski(disc.clone(), x.clone(), blob.clone(), 1000, 0)
+                .await,
+            Ok(true)
+        );
+        assert_eq!(
+            vdf.verify_n_wesolowski(disc, x, blob, 1001, 0).await,
+            Ok(false)
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancel_on_drop() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let vdf = AsyncVdf::new(1);
+
+        // The intermediates of the full evaluation are allocated first, so the number of
+        // iterations must stay reasonable
+        let num_iterations = 1 << 22;
+        let evaluation = vdf.evaluate_and_prove(disc.clone(), x.clone(), num_iterations);
+        assert!(timeout(Duration::from_millis(100), evaluation)
+            .await
+            .is_err());
+        let proof = vdf.prove(disc.clone(), x.clone(), x.clone(), num_iterations);
+        assert!(timeout(Duration::from_millis(100), proof).await.is_err());
+
+        // The dropped calls free their worker thread for the next one
+        let next = vdf.evaluate_and_prove(disc, x, 100);
+        assert!(timeout(Duration::from_secs(60), next)
+            .await
+            .unwrap()
+            .is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_max_jobs() {
+        let vdf = AsyncVdf::new(3);
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..12)
+            .map(|_| {
+                let (vdf, running, max_running) =
+                    (vdf.clone(), running.clone(), max_running.clone());
+                tokio::spawn(async move {
+                    vdf.run(move |_| {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_running.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(20));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    })
+                    .await
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), Ok(()));
+        }
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_own_threads() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .max_blocking_threads(1)
+            .enable_time()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            // The calls still run while the blocking pool of the runtime is busy
+            let (release, released) = std::sync::mpsc::channel::<()>();
+            let busy = tokio::task::spawn_blocking(move || released.recv());
+            let vdf = AsyncVdf::new(1);
+            let name = vdf.run(|_| Ok(thread::current().name().map(str::to_string)));
+            assert_eq!(
+                timeout(Duration::from_secs(10), name).await.unwrap(),
+                Ok(Some("chiavdf-async-0".to_string()))
+            );
+            release.send(()).unwrap();
+            busy.await.unwrap().unwrap();
+        });
+    }
+}
@@ -0,0 +1,373 @@
+// Port of the compressed form encoding in src/bqfc.c, byte-identical to `bqfc_serialize` and
+// `bqfc_deserialize`.
+
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+use super::constants::{form_size_for_bits, MAX_DISCRIMINANT_SIZE};
+use super::error::{ChiavdfError, ChiavdfResult};
+use super::fastcrypto::extended_gcd::extended_euclidean_algorithm;
+use super::xgcd_partial::xgcd_partial;
+
+// Flags stored in the first byte of a serialized form
+const B_SIGN: u8 = 1 << 0;
+const T_SIGN: u8 = 1 << 1;
+const IS_1: u8 = 1 << 2;
+const IS_GEN: u8 = 1 << 3;
+
+/// A form (a, b) in compressed representation: `a = a' * g`, `t` is the cofactor of `b` found by
+/// the partial extended Euclidean algorithm on `a` and `b`, reduced by `g = gcd(a, t)`, and
+/// `b0 = b / a'` is the part of `b` which cannot be recovered from `a`, `t` and the discriminant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompressedForm {
+    pub a: BigInt,
+    pub t: BigInt,
+    pub g: BigInt,
+    pub b0: BigInt,
+    pub b_sign: bool,
+}
+
+/// Compress the form (a, b), like `bqfc_compr`. `a` must be positive.
+pub fn compress(a: &BigInt, b: &BigInt) -> CompressedForm {
+    if a == b {
+        return CompressedForm {
+            a: a.clone(),
+            t: BigInt::zero(),
+            g: BigInt::zero(),
+            b0: BigInt::zero(),
+            b_sign: false,
+        };
+    }
+
+    let b_sign = b.is_negative();
+    // `mpz_xgcd_partial` sets the opposite sign for t
+    let t = -xgcd_partial(a, &b.abs(), &a.sqrt()).1;
+
+    let output = extended_euclidean_algorithm(a, &t, false);
+    let g = output.gcd;
+    let (a, t, b0) = if g.is_one() {
+        (a.clone(), t, BigInt::zero())
+    } else {
+        let b0 = b.abs() / &output.a_divided_by_gcd;
+        (output.a_divided_by_gcd, output.b_divided_by_gcd, b0)
+    };
+
+    CompressedForm {
+        a,
+        t,
+        g,
+        b0,
+        b_sign,
+    }
+}
+
+/// Recover (a, b) from a compressed form and the discriminant, like `bqfc_decompr`.
+pub fn decompress(discriminant: &BigInt, form: &CompressedForm) -> ChiavdfResult<(BigInt, BigInt)> {
+    if form.t.is_zero() {
+        return Ok((form.a.clone(), form.a.clone()));
+    }
+    if form.a.is_zero() {
+        return Err(deserialization_error());
+    }
+
+    let t = if form.t.is_negative() {
+        &form.t + &form.a
+    } else {
+        form.t.clone()
+    };
+    let output = extended_euclidean_algorithm(&form.a, &t, false);
+    if !output.gcd.is_one() {
+        return Err(deserialization_error());
+    }
+    let t_inv = output.y.mod_floor(&form.a);
+
+    // b = sqrt(t^2 * D mod a) / t mod a
+    let d = discriminant.mod_floor(&form.a);
+    let square = (&form.t * &form.t).mod_floor(&form.a) * d % &form.a;
+    let root = square.sqrt();
+    if &root * &root != square {
+        return Err(deserialization_error());
+    }
+    let mut b = root * t_inv % &form.a;
+
+    let a = if form.g > BigInt::one() {
+        &form.a * &form.g
+    } else {
+        form.a.clone()
+    };
+
+    if form.b0.is_positive() {
+        b += &form.a * &form.b0;
+    }
+    if form.b_sign {
+        b = -b;
+    }
+    Ok((a, b))
+}
+
+/// Serialize the form (a, b) of a discriminant of `d_bits` bits, like `bqfc_serialize`. The output
+/// is [form_size_for_bits] bytes long.
+pub fn serialize(a: &BigInt, b: &BigInt, d_bits: usize) -> ChiavdfResult<Vec<u8>> {
+    check_d_bits(d_bits)?;
+    let mut out = vec![0u8; form_size_for_bits(d_bits)];
+
+    // The identity (1, 1) and the generator (2, 1) only take a flag
+    if b.is_one() && a <= &BigInt::from(2) {
+        out[0] = if a == &BigInt::from(2) { IS_GEN } else { IS_1 };
+        return Ok(out);
+    }
+
+    if !a.is_positive() {
+        return Err(ChiavdfError::InvalidInput("a must be positive".to_string()));
+    }
+    let form = compress(a, b);
+    let d_bits = round_d_bits(d_bits);
+
+    out[0] = if form.b_sign { B_SIGN } else { 0 };
+    if form.t.is_negative() {
+        out[0] |= T_SIGN;
+    }
+    let g_size = (form.g.bits().max(1) as usize).div_ceil(8) - 1;
+    if g_size >= d_bits / 32 {
+        return Err(ChiavdfError::InvalidInput(
+            "Form is too large for the discriminant size".to_string(),
+        ));
+    }
+    out[1] = g_size as u8;
+
+    let mut offset = 2;
+    for (value, size) in [
+        (&form.a, d_bits / 16 - g_size),
+        (&form.t, d_bits / 32 - g_size),
+        (&form.g, g_size + 1),
+        (&form.b0, g_size + 1),
+    ] {
+        export(&mut out[offset..offset + size], value)?;
+        offset += size;
+    }
+    Ok(out)
+}
+
+/// Deserialize a form of a discriminant of `d_bits` bits, like `bqfc_deserialize`. The encoding
+/// must be canonical, i.e. exactly what [serialize] outputs for the decoded form.
+pub fn deserialize(
+    discriminant: &BigInt,
+    bytes: &[u8],
+    d_bits: usize,
+) -> ChiavdfResult<(BigInt, BigInt)> {
+    check_d_bits(d_bits)?;
+    if bytes.len() != form_size_for_bits(d_bits) {
+        return Err(deserialization_error());
+    }
+
+    if bytes[0] & (IS_1 | IS_GEN) != 0 {
+        let a = if bytes[0] & IS_GEN != 0 { 2 } else { 1 };
+        return Ok((BigInt::from(a), BigInt::one()));
+    }
+
+    let rounded_d_bits = round_d_bits(d_bits);
+    let g_size = bytes[1] as usize;
+    if g_size >= rounded_d_bits / 32 {
+        return Err(deserialization_error());
+    }
+
+    let mut offset = 2;
+    let mut import = |size: usize| {
+        let value = BigInt::from_bytes_le(Sign::Plus, &bytes[offset..offset + size]);
+        offset += size;
+        value
+    };
+    let a = import(rounded_d_bits / 16 - g_size);
+    let mut t = import(rounded_d_bits / 32 - g_size);
+    let g = import(g_size + 1);
+    let b0 = import(g_size + 1);
+    if bytes[0] & T_SIGN != 0 {
+        t = -t;
+    }
+    let form = CompressedForm {
+        a,
+        t,
+        g,
+        b0,
+        b_sign: bytes[0] & B_SIGN != 0,
+    };
+
+    let (a, b) = decompress(discriminant, &form)?;
+    if serialize(&a, &b, d_bits)? != bytes {
+        return Err(deserialization_error());
+    }
+    Ok((a, b))
+}
+
+fn round_d_bits(d_bits: usize) -> usize {
+    d_bits.div_ceil(32) * 32
+}
+
+fn check_d_bits(d_bits: usize) -> ChiavdfResult<()> {
+    if d_bits == 0 || d_bits > MAX_DISCRIMINANT_SIZE {
+        return Err(ChiavdfError::InvalidInput(format!(
+            "Discriminant size must be between 1 and {} bits",
+            MAX_DISCRIMINANT_SIZE
+        )));
+    }
+    Ok(())
+}
+
+/// Write the magnitude of `value` in little-endian order, zero padded to the size of `out`.
+fn export(out: &mut [u8], value: &BigInt) -> ChiavdfResult<()> {
+    let bytes = if value.is_zero() {
+        Vec::new()
+    } else {
+        value.magnitude().to_bytes_le()
+    };
+    if bytes.len() > out.len() {
+        return Err(ChiavdfError::InvalidInput(
+            "Form is too large for the discriminant size".to_string(),
+        ));
+    }
+    out[..bytes.len()].copy_from_slice(&bytes);
+    Ok(())
+}
+
+fn deserialization_error() -> ChiavdfError {
+    ChiavdfError::InvalidForm("Deserializing compressed form failed".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+    #[cfg(feature = "cpp")]
+    use num_bigint::BigUint;
+
+    use super::*;
+    #[cfg(feature = "cpp")]
+    use crate::c_bindings;
+    use crate::discriminant::Discriminant;
+
+    #[test]
+    fn test_fixed_encoding() {
+        // The generator to the power 0x1234, encoded by the C++ library
+        let discriminant = Discriminant::from_seed(b"bqfc", 512).unwrap();
+        let d = discriminant.to_bigint();
+        let bytes = hex!("03009cb5e568fdf299f535a5995b0835dde80d09f191fb891522a3d29cf74973cf2dabdc431de37723c11c189c6fd4424c260201");
+        let a: BigInt =
+            "41441222447277499701434719609858075972783535696853202791196968279304546511672"
+                .parse()
+                .unwrap();
+        let b: BigInt =
+            "-26127113895391828404368759217411211742104774169329013637668161729346591427935"
+                .parse()
+                .unwrap();
+
+        assert_eq!(
+            deserialize(&d, &bytes, 512).unwrap(),
+            (a.clone(), b.clone())
+        );
+        assert_eq!(serialize(&a, &b, 512).unwrap(), bytes);
+        assert_eq!(decompress(&d, &compress(&a, &b)).unwrap(), (a, b));
+
+        let mut identity = [0; 52];
+        identity[0] = IS_1;
+        let mut generator = identity;
+        generator[0] = IS_GEN;
+        assert_eq!(
+            serialize(&BigInt::one(), &BigInt::one(), 512).unwrap(),
+            identity
+        );
+        assert_eq!(
+            deserialize(&d, &generator, 512).unwrap(),
+            (BigInt::from(2), BigInt::one())
+        );
+
+        let mut corrupted = bytes;
+        corrupted[2] ^= 1;
+        assert!(deserialize(&d, &corrupted, 512).is_err());
+        assert!(deserialize(&d, &bytes[1..], 512).is_err());
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_matches_c_encoding() {
+        // Sizes which are and are not multiples of 32 bits
+        for d_bits in [256, 520, 1000, 1024, 2048, 4096] {
+            let discriminant = Discriminant::from_seed(b"bqfc", d_bits).unwrap();
+            let d = discriminant.to_bigint();
+            let generator = c_bindings::generator(&discriminant).unwrap();
+            let identity = c_bindings::identity(&discriminant).unwrap();
+
+            let mut forms = vec![identity, generator.clone()];
+            for exponent in [2u32, 3, 1234, 98765, u32::MAX] {
+                let exponent = BigUint::from(exponent).to_bytes_be();
+                forms.push(c_bindings::power(&discriminant, &generator, &exponent).unwrap());
+            }
+
+            for bytes in forms {
+                let (a, b, _) = c_bindings::decompress(&discriminant, &bytes).unwrap();
+                assert_eq!(serialize(&a, &b, d_bits).unwrap(), bytes);
+                assert_eq!(
+                    deserialize(&d, &bytes, d_bits).unwrap(),
+                    (a.clone(), b.clone())
+                );
+                assert_eq!(decompress(&d, &compress(&a, &b)).unwrap(), (a, b));
+                assert_eq!(bytes.len(), form_size_for_bits(d_bits));
+            }
+        }
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_lehmer_steps() {
+        // The 215th form of this sequence is compressed differently by the plain extended
+        // Euclidean algorithm than by `mpz_xgcd_partial`
+        let discriminant = Discriminant::from_seed(b"stress", 2048).unwrap();
+        let generator = c_bindings::generator(&discriminant).unwrap();
+        let mut form = generator.clone();
+        for i in 0..215u32 {
+            let exponent = (i * 7919 + 13).to_be_bytes();
+            let power = c_bindings::power(&discriminant, &generator, &exponent).unwrap();
+            form = c_bindings::multiply(&discriminant, &form, &power).unwrap();
+            let (a, b, _) = c_bindings::decompress(&discriminant, &form).unwrap();
+            assert_eq!(serialize(&a, &b, 2048).unwrap(), form);
+        }
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_corrupted_encodings() {
+        let discriminant = Discriminant::from_seed(b"bqfc", 1024).unwrap();
+        let d = discriminant.to_bigint();
+        let generator = c_bindings::generator(&discriminant).unwrap();
+        let form = c_bindings::power(&discriminant, &generator, &[0x12, 0x34]).unwrap();
+
+        for index in 0..form_size_for_bits(1024) {
+            for flip in [0x01, 0x80] {
+                let mut corrupted = form.clone();
+                corrupted[index] ^= flip;
+                match (
+                    c_bindings::decompress(&discriminant, &corrupted),
+                    deserialize(&d, &corrupted, 1024),
+                ) {
+                    // The C++ side reduces the decoded form, which may shift b by a multiple of 2a
+                    (Ok((a, b, _)), Ok((decoded_a, decoded_b))) => {
+                        assert_eq!(decoded_a, a);
+                        assert!((decoded_b - b).is_multiple_of(&(2 * a)), "byte {}", index);
+                    }
+                    (Err(_), Err(_)) => {}
+                    // The C++ side also rejects encodings of (a, b) which are not a form of the
+                    // discriminant, while `bqfc_deserialize` alone accepts them
+                    (Err(_), Ok((a, b))) => {
+                        assert!(!(&b * &b - &d).is_multiple_of(&(4 * &a)), "byte {}", index)
+                    }
+                    (Ok(_), Err(_)) => panic!("byte {} is only rejected in Rust", index),
+                }
+            }
+        }
+
+        assert!(deserialize(&d, &form[..form.len() - 1], 1024).is_err());
+        assert!(deserialize(&d, &[form.clone(), vec![0]].concat(), 1024).is_err());
+        assert!(serialize(&BigInt::from(3), &BigInt::from(1), 0).is_err());
+        assert!(serialize(&BigInt::from(3), &BigInt::from(1), 4097).is_err());
+        assert!(serialize(&BigInt::from(-3), &BigInt::from(5), 1024).is_err());
+    }
+}
@@ -5,131 +5,668 @@
 
 extern crate link_cplusplus;
 
-use super::constants::FORM_SIZE;
-mod bindings {
+use std::any::Any;
+use std::ffi::{c_void, CStr};
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::Signed;
+use rand::RngCore;
+
+use super::constants::{form_size_for_bits, B_BITS, B_BYTES, MAX_DISCRIMINANT_SIZE};
+use super::discriminant::Discriminant;
+use super::error::{ChiavdfError, ChiavdfResult};
+pub(crate) mod bindings {
     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 }
 
-pub fn create_discriminant(seed: &[u8], result: &mut [u8]) -> bool {
-    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and None is returned if so.
+/// The error recorded by the C++ side for the last failed call on this thread, if any.
+pub(crate) fn last_error() -> Option<ChiavdfError> {
+    // SAFETY: The message is a null-terminated string owned by the C++ side which stays valid
+    // until the next failing call on this thread. It is copied before returning.
     unsafe {
+        let message = CStr::from_ptr(bindings::chiavdf_last_error_message())
+            .to_string_lossy()
+            .into_owned();
+        match bindings::chiavdf_last_error_kind() as u32 {
+            bindings::CHIAVDF_ERROR_NONE => None,
+            bindings::CHIAVDF_ERROR_BAD_ALLOC => Some(ChiavdfError::OutOfMemory),
+            bindings::CHIAVDF_ERROR_CANCELLED => Some(ChiavdfError::Cancelled),
+            bindings::CHIAVDF_ERROR_EXCEPTION => {
+                Some(ChiavdfError::from_exception_message(message))
+            }
+            _ => Some(ChiavdfError::Exception(message)),
+        }
+    }
+}
+
+fn failure() -> ChiavdfError {
+    last_error().unwrap_or_else(|| ChiavdfError::Exception("unknown exception".to_string()))
+}
+
+/// Copy the content of a `ByteArray` returned by the C++ side and free it.
+///
+/// # Safety
+///
+/// `array` must come from a wrapper returning a `ByteArray` and not have been freed yet.
+pub(crate) unsafe fn take_byte_array(array: bindings::ByteArray) -> ChiavdfResult<Vec<u8>> {
+    if array.data.is_null() {
+        return Err(failure());
+    }
+    let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
+    bindings::delete_byte_array(array);
+    Ok(result)
+}
+
+/// The C++ side reads forms with the size derived from the discriminant.
+pub(crate) fn check_form_size(discriminant: &Discriminant, x_s: &[u8]) -> ChiavdfResult<()> {
+    if x_s.len() != discriminant.form_size() {
+        return Err(ChiavdfError::InvalidInput(
+            "Forms must have the size of the discriminant".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// The C++ side reads every form with the length of the first one, so they must all match.
+fn check_form_sizes(x_s: &[u8], others: &[&[u8]]) -> ChiavdfResult<()> {
+    if others.iter().any(|other| other.len() != x_s.len()) {
+        return Err(ChiavdfError::InvalidInput(
+            "All forms must have the same size".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub fn create_discriminant(seed: &[u8], result: &mut [u8]) -> ChiavdfResult<()> {
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns false and records the error.
+    let success = unsafe {
         bindings::create_discriminant_wrapper(
             seed.as_ptr(),
             seed.len(),
             result.len() * 8,
             result.as_mut_ptr(),
         )
+    };
+    if !success {
+        return Err(failure());
     }
+    Ok(())
 }
 
-pub fn n_prove(discriminant: &[u8], x_s: &[u8], num_iterations: u64) -> Option<Vec<u8>> {
-    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
-    unsafe {
-        let array = bindings::prove_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
-            x_s.as_ptr(),
-            x_s.len(),
-            num_iterations,
+/// Progress of a running evaluation, reported by the `_with_progress` functions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Progress {
+    /// Number of squarings done so far.
+    pub iterations: u64,
+    /// Time since the evaluation started.
+    pub elapsed: Duration,
+    /// Squarings per second since the previous report.
+    pub iterations_per_second: f64,
+}
+
+/// The state of an [evaluate_to_prove] evaluation, reported by the `_with_checkpoints` functions.
+/// [resume_evaluation] continues the evaluation from it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The form the evaluation started from.
+    pub x: Vec<u8>,
+    /// The total number of squarings of the evaluation.
+    pub num_iterations: u64,
+    /// The number of squarings done.
+    pub iterations: u64,
+    /// The form after `iterations` squarings.
+    pub y: Vec<u8>,
+    /// The intermediates collected so far, in the layout returned by [evaluate_to_prove].
+    pub intermediates: Vec<u8>,
+    /// The k parameter of the evaluation, the intermediates are kept every `k * l` squarings.
+    pub k: u32,
+    /// The l parameter of the evaluation.
+    pub l: u32,
+}
+
+impl Checkpoint {
+    const HEADER_SIZE: usize = 4 + 8 + 8 + 4 + 4;
+
+    /// Encode as the form size, `num_iterations`, `iterations`, `k` and `l` (big-endian), followed
+    /// by `x`, `y` and the intermediates.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            Self::HEADER_SIZE + self.x.len() + self.y.len() + self.intermediates.len(),
         );
-        if array.data.is_null() {
-            return None;
+        bytes.extend_from_slice(&(self.x.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&self.num_iterations.to_be_bytes());
+        bytes.extend_from_slice(&self.iterations.to_be_bytes());
+        bytes.extend_from_slice(&self.k.to_be_bytes());
+        bytes.extend_from_slice(&self.l.to_be_bytes());
+        bytes.extend_from_slice(&self.x);
+        bytes.extend_from_slice(&self.y);
+        bytes.extend_from_slice(&self.intermediates);
+        bytes
+    }
+
+    /// Decode the output of [Checkpoint::to_bytes]. The forms are only checked when resuming.
+    pub fn from_bytes(bytes: &[u8]) -> ChiavdfResult<Self> {
+        let malformed = || ChiavdfError::InvalidInput("Malformed checkpoint".to_string());
+        let (header, forms) = bytes
+            .split_at_checked(Self::HEADER_SIZE)
+            .ok_or_else(malformed)?;
+        let form_size = u32::from_be_bytes(header[..4].try_into().unwrap()) as usize;
+        if form_size == 0 || forms.len() < 2 * form_size || !forms.len().is_multiple_of(form_size) {
+            return Err(malformed());
         }
-        let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        Some(result)
+        Ok(Self {
+            num_iterations: u64::from_be_bytes(header[4..12].try_into().unwrap()),
+            iterations: u64::from_be_bytes(header[12..20].try_into().unwrap()),
+            k: u32::from_be_bytes(header[20..24].try_into().unwrap()),
+            l: u32::from_be_bytes(header[24..28].try_into().unwrap()),
+            x: forms[..form_size].to_vec(),
+            y: forms[form_size..2 * form_size].to_vec(),
+            intermediates: forms[2 * form_size..].to_vec(),
+        })
     }
 }
 
-pub fn evaluate_and_prove(
-    discriminant: &[u8],
+type OnCheckpoint<'a> = &'a mut dyn FnMut(&Checkpoint);
+
+/// Optional hooks into the squaring loop of the slow prover.
+#[derive(Default)]
+struct Hooks<'a> {
+    cancel: Option<&'a AtomicBool>,
+    // Reporting interval in squarings and callback
+    progress: Option<(u64, &'a mut dyn FnMut(Progress))>,
+    // Checkpoint interval in squarings, checkpoint to update and callback
+    checkpoint: Option<(u64, Checkpoint, OnCheckpoint<'a>)>,
+}
+
+/// The context passed to the C++ side along with the callbacks.
+struct HookState<'a> {
+    start: Instant,
+    last_progress: (u64, Instant),
+    on_progress: Option<&'a mut dyn FnMut(Progress)>,
+    checkpoint: Option<(Checkpoint, OnCheckpoint<'a>)>,
+    // A panic of a callback, resumed once back from the C++ side
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl HookState<'_> {
+    /// Run a callback and return whether the evaluation should go on. Unwinding into the C++
+    /// side is undefined behavior, so a panic stops the evaluation and is kept for later.
+    fn run(&mut self, callback: impl FnOnce(&mut Self)) -> bool {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| callback(&mut *self)));
+        match result {
+            Ok(()) => true,
+            Err(payload) => {
+                self.panic = Some(payload);
+                false
+            }
+        }
+    }
+}
+
+/// The `ProgressCallback` passed to the C++ side.
+unsafe extern "C" fn report_progress(context: *mut c_void, iterations: u64) -> bool {
+    // SAFETY: The context is the `HookState` of the ongoing call, which outlives it.
+    let state = unsafe { &mut *context.cast::<HookState>() };
+    let now = Instant::now();
+    let (last_iterations, last_time) = state.last_progress;
+    let progress = Progress {
+        iterations,
+        elapsed: now - state.start,
+        iterations_per_second: (iterations - last_iterations) as f64
+            / (now - last_time).as_secs_f64(),
+    };
+    state.last_progress = (iterations, now);
+    state.run(|state| (state.on_progress.as_mut().unwrap())(progress))
+}
+
+/// The `CheckpointCallback` passed to the C++ side.
+#[allow(clippy::too_many_arguments)]
+unsafe extern "C" fn report_checkpoint(
+    context: *mut c_void,
+    iterations: u64,
+    k: i32,
+    l: i32,
+    y_s: *const u8,
+    form_size: usize,
+    intermediates: *const u8,
+    intermediates_size: usize,
+) -> bool {
+    // SAFETY: The context is the `HookState` of the ongoing call, which outlives it. The buffers
+    // have the given sizes and stay valid during the call. An empty vector may have a null data
+    // pointer, which `from_raw_parts` does not accept.
+    let (state, y_s, intermediates) = unsafe {
+        (
+            &mut *context.cast::<HookState>(),
+            std::slice::from_raw_parts(y_s, form_size),
+            match intermediates_size {
+                0 => &[],
+                size => std::slice::from_raw_parts(intermediates, size),
+            },
+        )
+    };
+    state.run(|state| {
+        let (checkpoint, on_checkpoint) = state.checkpoint.as_mut().unwrap();
+        checkpoint.iterations = iterations;
+        checkpoint.k = k as u32;
+        checkpoint.l = l as u32;
+        checkpoint.y = y_s.to_vec();
+        checkpoint.intermediates.extend_from_slice(intermediates);
+        on_checkpoint(checkpoint);
+    })
+}
+
+/// Call a wrapper of the slow prover with the C++ side of the given hooks.
+fn run_slow(
+    hooks: Hooks,
+    call: impl FnOnce(*const bindings::EvalHooks) -> bindings::ByteArray,
+) -> ChiavdfResult<Vec<u8>> {
+    let start = Instant::now();
+    let mut c_hooks = bindings::EvalHooks {
+        cancelled: hooks
+            .cancel
+            .map_or(ptr::null(), |flag| flag.as_ptr().cast_const()),
+        progress: None,
+        progress_interval: 0,
+        checkpoint: None,
+        checkpoint_interval: 0,
+        context: ptr::null_mut(),
+    };
+    let mut state = HookState {
+        start,
+        last_progress: (0, start),
+        on_progress: None,
+        checkpoint: None,
+        panic: None,
+    };
+    if let Some((interval, on_progress)) = hooks.progress {
+        c_hooks.progress = Some(report_progress);
+        c_hooks.progress_interval = interval;
+        state.on_progress = Some(on_progress);
+    }
+    if let Some((interval, checkpoint, on_checkpoint)) = hooks.checkpoint {
+        c_hooks.checkpoint = Some(report_checkpoint);
+        c_hooks.checkpoint_interval = interval;
+        state.checkpoint = Some((checkpoint, on_checkpoint));
+    }
+    c_hooks.context = (&mut state as *mut HookState).cast();
+
+    // SAFETY: The flag and the state outlive the call, and the flag is only accessed atomically
+    // on both sides. Exceptions are caught on the C++ side, which returns a null `data` and
+    // records the error.
+    let result = unsafe { take_byte_array(call(&c_hooks)) };
+    if let Some(payload) = state.panic {
+        panic::resume_unwind(payload);
+    }
+    result
+}
+
+/// The signature shared by `prove_wrapper` and `evaluate_to_prove_wrapper`.
+type SlowWrapper = unsafe extern "C" fn(
+    *const u8,
+    usize,
+    *const u8,
+    usize,
+    u64,
+    *const bindings::EvalHooks,
+) -> bindings::ByteArray;
+
+fn run_wrapper(
+    wrapper: SlowWrapper,
+    discriminant: &Discriminant,
     x_s: &[u8],
     num_iterations: u64,
-) -> Option<(Vec<u8>, Vec<u8>)> {
+    hooks: Hooks,
+) -> ChiavdfResult<Vec<u8>> {
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
-    unsafe {
-        let array = bindings::prove_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
+    run_slow(hooks, |c_hooks| unsafe {
+        wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
             x_s.as_ptr(),
             x_s.len(),
             num_iterations,
-        );
-        if array.data.is_null() {
-            return None;
-        }
-        let mut result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        let proof = result.split_off(FORM_SIZE);
-        Some((result, proof))
-    }
+            c_hooks,
+        )
+    })
+}
+
+fn prove_slow(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    hooks: Hooks,
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let mut result = run_wrapper(
+        bindings::prove_wrapper,
+        discriminant,
+        x_s,
+        num_iterations,
+        hooks,
+    )?;
+    let proof = result.split_off(discriminant.form_size());
+    Ok((result, proof))
+}
+
+fn eval_slow(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    hooks: Hooks,
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let mut result = run_wrapper(
+        bindings::evaluate_to_prove_wrapper,
+        discriminant,
+        x_s,
+        num_iterations,
+        hooks,
+    )?;
+    let form_size = discriminant.form_size();
+    assert!(result.len().is_multiple_of(form_size));
+    let intermediates: Vec<u8> = result.split_off(form_size);
+    Ok((result, intermediates))
+}
+
+pub fn n_prove(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+) -> ChiavdfResult<Vec<u8>> {
+    run_wrapper(
+        bindings::prove_wrapper,
+        discriminant,
+        x_s,
+        num_iterations,
+        Hooks::default(),
+    )
+}
+
+/// Like [n_prove], but stops with [ChiavdfError::Cancelled] once `cancel` is set. The flag is
+/// checked at every squaring, so the call returns shortly after it is set from another thread.
+pub fn n_prove_cancellable(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    cancel: &AtomicBool,
+) -> ChiavdfResult<Vec<u8>> {
+    let hooks = Hooks {
+        cancel: Some(cancel),
+        ..Default::default()
+    };
+    run_wrapper(
+        bindings::prove_wrapper,
+        discriminant,
+        x_s,
+        num_iterations,
+        hooks,
+    )
+}
+
+pub fn evaluate_and_prove(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    prove_slow(discriminant, x_s, num_iterations, Hooks::default())
+}
+
+/// Like [evaluate_and_prove], but stops with [ChiavdfError::Cancelled] once `cancel` is set.
+pub fn evaluate_and_prove_cancellable(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    cancel: &AtomicBool,
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let hooks = Hooks {
+        cancel: Some(cancel),
+        ..Default::default()
+    };
+    prove_slow(discriminant, x_s, num_iterations, hooks)
+}
+
+/// Like [evaluate_and_prove], and calls `on_progress` every `interval` squarings from the
+/// evaluating thread. Also stops with [ChiavdfError::Cancelled] once `cancel` is set, if given.
+/// A panic of `on_progress` stops the evaluation and is propagated. An `interval` of 0 disables
+/// the reports.
+pub fn evaluate_and_prove_with_progress(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    interval: u64,
+    cancel: Option<&AtomicBool>,
+    mut on_progress: impl FnMut(Progress),
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let hooks = Hooks {
+        cancel,
+        progress: Some((interval, &mut on_progress)),
+        ..Default::default()
+    };
+    prove_slow(discriminant, x_s, num_iterations, hooks)
+}
+
+/// Square `x_s` `num_iterations` times and return only the result, without keeping the
+/// intermediates of [evaluate_to_prove]. It can be proven later with [prove].
+pub fn evaluate(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+) -> ChiavdfResult<Vec<u8>> {
+    run_wrapper(
+        bindings::evaluate_wrapper,
+        discriminant,
+        x_s,
+        num_iterations,
+        Hooks::default(),
+    )
 }
 
 pub fn evaluate_to_prove(
-    discriminant: &[u8],
+    discriminant: &Discriminant,
     x_s: &[u8],
     num_iterations: u64,
-) -> Option<(Vec<u8>, Vec<u8>)> {
-    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
-    unsafe {
-        let array = bindings::evaluate_to_prove_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
-            x_s.as_ptr(),
-            x_s.len(),
-            num_iterations,
-        );
-        if array.data.is_null() {
-            return None;
-        }
-        let mut result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        let result_length = result.len();
-        assert!(result_length % FORM_SIZE == 0);
-        let intermediates: Vec<u8> = result.split_off(FORM_SIZE);
-        Some((result, intermediates))
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    eval_slow(discriminant, x_s, num_iterations, Hooks::default())
+}
+
+/// Like [evaluate_to_prove], but stops with [ChiavdfError::Cancelled] once `cancel` is set.
+pub fn evaluate_to_prove_cancellable(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    cancel: &AtomicBool,
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let hooks = Hooks {
+        cancel: Some(cancel),
+        ..Default::default()
+    };
+    eval_slow(discriminant, x_s, num_iterations, hooks)
+}
+
+/// Like [evaluate_to_prove], with progress reports and cancellation as in
+/// [evaluate_and_prove_with_progress].
+pub fn evaluate_to_prove_with_progress(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    interval: u64,
+    cancel: Option<&AtomicBool>,
+    mut on_progress: impl FnMut(Progress),
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let hooks = Hooks {
+        cancel,
+        progress: Some((interval, &mut on_progress)),
+        ..Default::default()
+    };
+    eval_slow(discriminant, x_s, num_iterations, hooks)
+}
+
+/// Like [evaluate_to_prove], and calls `on_checkpoint` every `interval` squarings with the state
+/// of the evaluation, from which [resume_evaluation] continues it. Also stops with
+/// [ChiavdfError::Cancelled] once `cancel` is set, if given. A panic of `on_checkpoint` stops the
+/// evaluation and is propagated. An `interval` of 0 disables the checkpoints.
+pub fn evaluate_to_prove_with_checkpoints(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    interval: u64,
+    cancel: Option<&AtomicBool>,
+    mut on_checkpoint: impl FnMut(&Checkpoint),
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let checkpoint = Checkpoint {
+        x: x_s.to_vec(),
+        num_iterations,
+        iterations: 0,
+        y: x_s.to_vec(),
+        intermediates: Vec::new(),
+        k: 0,
+        l: 0,
+    };
+    let hooks = Hooks {
+        cancel,
+        checkpoint: Some((interval, checkpoint, &mut on_checkpoint)),
+        ..Default::default()
+    };
+    eval_slow(discriminant, x_s, num_iterations, hooks)
+}
+
+/// Continue an evaluation from a checkpoint and return the same result as [evaluate_to_prove]
+/// from the start. Fails if the checkpoint does not belong to an evaluation of `discriminant`.
+pub fn resume_evaluation(
+    discriminant: &Discriminant,
+    checkpoint: &Checkpoint,
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    resume_slow(discriminant, checkpoint, Hooks::default())
+}
+
+/// Like [resume_evaluation], with checkpoints and cancellation as in
+/// [evaluate_to_prove_with_checkpoints]. The checkpoints include the intermediates of `checkpoint`.
+pub fn resume_evaluation_with_checkpoints(
+    discriminant: &Discriminant,
+    checkpoint: &Checkpoint,
+    interval: u64,
+    cancel: Option<&AtomicBool>,
+    mut on_checkpoint: impl FnMut(&Checkpoint),
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let hooks = Hooks {
+        cancel,
+        checkpoint: Some((interval, checkpoint.clone(), &mut on_checkpoint)),
+        ..Default::default()
+    };
+    resume_slow(discriminant, checkpoint, hooks)
+}
+
+fn resume_slow(
+    discriminant: &Discriminant,
+    checkpoint: &Checkpoint,
+    hooks: Hooks,
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    let form_size = discriminant.form_size();
+    if checkpoint.x.len() != form_size
+        || checkpoint.y.len() != form_size
+        || !checkpoint.intermediates.len().is_multiple_of(form_size)
+    {
+        return Err(ChiavdfError::InvalidInput(
+            "The checkpoint forms do not match the discriminant".to_string(),
+        ));
     }
+    let (k, l) = match (i32::try_from(checkpoint.k), i32::try_from(checkpoint.l)) {
+        (Ok(k), Ok(l)) => (k, l),
+        _ => {
+            return Err(ChiavdfError::InvalidInput(
+                "Checkpoint does not match the evaluation parameters".to_string(),
+            ))
+        }
+    };
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    let mut result = run_slow(hooks, |c_hooks| unsafe {
+        bindings::resume_evaluation_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            checkpoint.y.as_ptr(),
+            form_size,
+            checkpoint.intermediates.as_ptr(),
+            checkpoint.intermediates.len(),
+            checkpoint.iterations,
+            checkpoint.num_iterations,
+            k,
+            l,
+            c_hooks,
+        )
+    })?;
+    let intermediates = result.split_off(form_size);
+    Ok((result, intermediates))
+}
+
+pub fn prove(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    num_iterations: u64,
+) -> ChiavdfResult<Vec<u8>> {
+    prove_only(discriminant, x_s, y_s, num_iterations, None)
 }
 
-pub fn prove(discriminant: &[u8], x_s: &[u8], y_s: &[u8], num_iterations: u64) -> Option<Vec<u8>> {
+/// Like [prove], but stops with [ChiavdfError::Cancelled] once `cancel` is set. The flag is
+/// checked at every squaring of the exponentiation.
+pub fn prove_cancellable(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    num_iterations: u64,
+    cancel: &AtomicBool,
+) -> ChiavdfResult<Vec<u8>> {
+    prove_only(discriminant, x_s, y_s, num_iterations, Some(cancel))
+}
+
+fn prove_only(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    num_iterations: u64,
+    cancel: Option<&AtomicBool>,
+) -> ChiavdfResult<Vec<u8>> {
+    check_form_sizes(x_s, &[y_s])?;
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
     unsafe {
         let array = bindings::prove_only_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
             x_s.as_ptr(),
             y_s.as_ptr(),
             x_s.len(),
             num_iterations,
+            cancel.map_or(ptr::null(), |flag| flag.as_ptr().cast_const()),
         );
-        if array.data.is_null() {
-            return None;
-        }
-        let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        Some(result)
+        take_byte_array(array)
     }
 }
 
 pub fn prove_ext(
-    discriminant: &[u8],
+    discriminant: &Discriminant,
     x_s: &[u8],
     y_s: &[u8],
     inter_s: &[u8],
     num_iterations: u64,
-) -> Option<Vec<u8>> {
+) -> ChiavdfResult<Vec<u8>> {
+    check_form_sizes(x_s, &[y_s])?;
+    if !inter_s.len().is_multiple_of(discriminant.form_size()) {
+        return Err(ChiavdfError::InvalidInput(
+            "Intermediates must be a sequence of forms".to_string(),
+        ));
+    }
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
     unsafe {
-        assert!(inter_s.len() % FORM_SIZE == 0);
         let array = bindings::prove_int_only_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
             x_s.as_ptr(),
             y_s.as_ptr(),
             x_s.len(),
@@ -137,180 +674,737 @@ pub fn prove_ext(
             inter_s.len(),
             num_iterations,
         );
-        if array.data.is_null() {
-            return None;
+        take_byte_array(array)
+    }
+}
+
+/// Prove `num_iterations` squarings of `x_s` as an N-Wesolowski proof with `recursion` extra
+/// segments, the blob accepted by [verify_n_wesolowski] with the same `recursion`. This is the
+/// `ProveTwoWeso` prover of the timelord's `vdf_client` on its fast squaring loop, each extra
+/// segment taking two thirds of the remaining squarings rounded down to a multiple of 100, which
+/// must not be zero.
+#[cfg(feature = "timelord")]
+pub fn prove_n_wesolowski(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    num_iterations: u64,
+    recursion: u64,
+) -> ChiavdfResult<Vec<u8>> {
+    check_form_size(discriminant, x_s)?;
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
+    unsafe {
+        let array = bindings::prove_n_wesolowski_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            x_s.as_ptr(),
+            x_s.len(),
+            num_iterations,
+            recursion,
+        );
+        take_byte_array(array)
+    }
+}
+
+/// The N-Wesolowski session of the timelord's `vdf_client`, without the subprocess: squares x on
+/// the fast squaring loop until stopped, while its provers prove the segments squared so far, and
+/// proves any number of squarings on request. Proofs can be requested from several threads at once.
+#[cfg(feature = "timelord")]
+pub struct ProverSession {
+    session: ptr::NonNull<bindings::ProverSession>,
+    form_size: usize,
+}
+
+// SAFETY: The C++ session guards its state with mutexes and atomics.
+#[cfg(feature = "timelord")]
+unsafe impl Send for ProverSession {}
+#[cfg(feature = "timelord")]
+unsafe impl Sync for ProverSession {}
+
+#[cfg(feature = "timelord")]
+impl ProverSession {
+    /// Start squaring `x_s`.
+    pub fn start(discriminant: &Discriminant, x_s: &[u8]) -> ChiavdfResult<Self> {
+        check_form_size(discriminant, x_s)?;
+        // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+        // Exceptions are caught on the C++ side, which returns null and records the error.
+        let session = unsafe {
+            bindings::prover_session_start(
+                discriminant.as_bytes().as_ptr(),
+                discriminant.as_bytes().len(),
+                x_s.as_ptr(),
+                x_s.len(),
+            )
+        };
+        let session = ptr::NonNull::new(session).ok_or_else(failure)?;
+        Ok(Self {
+            session,
+            form_size: x_s.len(),
+        })
+    }
+
+    /// The number of squarings done so far.
+    pub fn iterations(&self) -> u64 {
+        // SAFETY: The session is valid until dropped.
+        unsafe { bindings::prover_session_iterations(self.session.as_ptr()) }
+    }
+
+    /// Wait until `num_iterations` squarings are done and proven, and return the result, its
+    /// N-Wesolowski proof and the recursion to pass to [verify_n_wesolowski] with both. Fails with
+    /// [ChiavdfError::Cancelled] if the session is stopped before, and right away past the
+    /// squarings the timelord does, a little under 800 million.
+    pub fn prove(&self, num_iterations: u64) -> ChiavdfResult<(Vec<u8>, Vec<u8>, u64)> {
+        if num_iterations == 0 {
+            return Err(ChiavdfError::InvalidInput(
+                "Cannot prove zero iterations".to_string(),
+            ));
+        }
+        let mut witness_type = 0;
+        // SAFETY: The session is valid until dropped. Exceptions are caught on the C++ side, which
+        // returns a null `data` and records the error.
+        let mut y = unsafe {
+            take_byte_array(bindings::prover_session_prove(
+                self.session.as_ptr(),
+                num_iterations,
+                &mut witness_type,
+            ))?
+        };
+        let proof = y.split_off(self.form_size);
+        Ok((y, proof, witness_type.into()))
+    }
+
+    /// Stop squaring and proving, and return once the threads are done. The pending proofs and the
+    /// later ones fail.
+    pub fn stop(&self) -> ChiavdfResult<()> {
+        // SAFETY: The session is valid until dropped. Exceptions are caught on the C++ side, which
+        // returns false and records the error.
+        if unsafe { bindings::prover_session_stop(self.session.as_ptr()) } {
+            Ok(())
+        } else {
+            Err(failure())
         }
-        let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        Some(result)
+    }
+}
+
+#[cfg(feature = "timelord")]
+impl Drop for ProverSession {
+    fn drop(&mut self) {
+        // SAFETY: The session is not used after this, and no call can be pending with `&mut self`.
+        unsafe { bindings::prover_session_free(self.session.as_ptr()) }
     }
 }
 
 pub fn verify(
-    discriminant: &[u8],
+    discriminant: &Discriminant,
     x_s: &[u8],
     y_s: &[u8],
     proof: &[u8],
     num_iterations: u64,
-) -> bool {
+) -> ChiavdfResult<bool> {
+    check_form_sizes(x_s, &[y_s, proof])?;
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and false is returned if so.
-    unsafe {
+    // Exceptions are caught on the C++ side, which returns false and records the error.
+    let valid = unsafe {
         bindings::verify_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
             x_s.as_ptr(),
             y_s.as_ptr(),
             proof.as_ptr(),
             proof.len(),
             num_iterations,
         )
+    };
+    // An invalid proof is not an error, only a failure to decode the inputs is
+    match last_error() {
+        Some(error) if !valid => Err(error),
+        _ => Ok(valid),
+    }
+}
+
+/// The x, y, proof and number of iterations of a Wesolowski proof, the arguments of [verify].
+pub type WesolowskiProof<'a> = (&'a [u8], &'a [u8], &'a [u8], u64);
+
+/// The byte size of the random weights of [verify_batch]. A batch with invalid proofs passes the
+/// combined check with a probability of about 2^-128.
+const BATCH_WEIGHT_SIZE: usize = 16;
+
+/// Verify Wesolowski proofs `(x, y, proof, num_iterations)` of the same discriminant and return
+/// what [verify] returns for each of them. The proofs are first checked at once with a random
+/// linear combination, which shares most of the work between them. If that check fails, each
+/// proof is verified on its own to find the invalid ones.
+pub fn verify_batch(
+    discriminant: &Discriminant,
+    proofs: &[WesolowskiProof],
+) -> Vec<ChiavdfResult<bool>> {
+    let form_size = discriminant.form_size();
+    let has_form_sizes = proofs.iter().all(|(x_s, y_s, proof, _)| {
+        x_s.len() == form_size && y_s.len() == form_size && proof.len() == form_size
+    });
+    if has_form_sizes && !proofs.is_empty() {
+        let forms: Vec<u8> = proofs
+            .iter()
+            .flat_map(|(x_s, y_s, proof, _)| [*x_s, *y_s, *proof])
+            .flatten()
+            .copied()
+            .collect();
+        let num_iterations: Vec<u64> = proofs.iter().map(|proof| proof.3).collect();
+        let mut weights = vec![0; proofs.len() * BATCH_WEIGHT_SIZE];
+        rand::rng().fill_bytes(&mut weights);
+        // SAFETY: The forms, iterations and weights are sized for `proofs.len()` proofs.
+        // Exceptions are caught on the C++ side, which returns false and records the error.
+        let valid = unsafe {
+            bindings::verify_batch_wrapper(
+                discriminant.as_bytes().as_ptr(),
+                discriminant.as_bytes().len(),
+                forms.as_ptr(),
+                form_size,
+                num_iterations.as_ptr(),
+                weights.as_ptr(),
+                BATCH_WEIGHT_SIZE,
+                proofs.len(),
+            )
+        };
+        if valid {
+            return vec![Ok(true); proofs.len()];
+        }
     }
+    proofs
+        .iter()
+        .map(|(x_s, y_s, proof, num_iterations)| {
+            verify(discriminant, x_s, y_s, proof, *num_iterations)
+        })
+        .collect()
 }
 
 pub fn verify_n_wesolowski(
-    discriminant: &[u8],
+    discriminant: &Discriminant,
     x_s: &[u8],
     proof: &[u8],
     num_iterations: u64,
     recursion: u64,
-) -> bool {
+) -> ChiavdfResult<bool> {
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and false is returned if so.
-    unsafe {
+    // Exceptions are caught on the C++ side, which returns false and records the error.
+    let valid = unsafe {
         bindings::verify_n_wesolowski_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            x_s.as_ptr(),
+            proof.as_ptr(),
+            proof.len(),
+            num_iterations,
+            recursion,
+        )
+    };
+    match last_error() {
+        Some(error) if !valid => Err(error),
+        _ => Ok(valid),
+    }
+}
+
+/// Derive the discriminant of `disc_size_bits` bits from `challenge`, as [Discriminant::from_seed]
+/// does, and verify an N-Wesolowski proof with it in a single call.
+pub fn create_discriminant_and_verify_n_wesolowski(
+    challenge: &[u8],
+    disc_size_bits: usize,
+    x_s: &[u8],
+    proof: &[u8],
+    num_iterations: u64,
+    recursion: u64,
+) -> ChiavdfResult<bool> {
+    if disc_size_bits == 0 || disc_size_bits > MAX_DISCRIMINANT_SIZE {
+        return Err(ChiavdfError::InvalidInput(
+            "Unsupported discriminant size".to_string(),
+        ));
+    }
+    if x_s.len() != form_size_for_bits(disc_size_bits) {
+        return Err(ChiavdfError::InvalidInput(
+            "Forms must have the size of the discriminant".to_string(),
+        ));
+    }
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns false and records the error.
+    let valid = unsafe {
+        bindings::create_discriminant_and_verify_n_wesolowski_wrapper(
+            challenge.as_ptr(),
+            challenge.len(),
+            disc_size_bits,
             x_s.as_ptr(),
+            x_s.len(),
             proof.as_ptr(),
             proof.len(),
             num_iterations,
             recursion,
         )
+    };
+    match last_error() {
+        Some(error) if !valid => Err(error),
+        _ => Ok(valid),
     }
 }
 
-pub fn from_ab(discriminant: &[u8], a: &[u8], b: &[u8]) -> Option<Vec<u8>> {
+/// Verify an N-Wesolowski proof in the compact format, in which the output y is replaced by its
+/// challenge `b` (see [get_b_from_n_wesolowski]) and `proof` is the blob without its leading y.
+/// Return y if the proof is valid. A blob of the wrong size for `recursion` is invalid.
+pub fn verify_n_wesolowski_with_b(
+    discriminant: &Discriminant,
+    b: &[u8],
+    x_s: &[u8],
+    proof: &[u8],
+    num_iterations: u64,
+    recursion: u64,
+) -> ChiavdfResult<Option<Vec<u8>>> {
+    check_form_size(discriminant, x_s)?;
+    let mut y_s = vec![0; discriminant.form_size()];
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
+    // Exceptions are caught on the C++ side, which returns false and records the error.
+    let valid = unsafe {
+        bindings::verify_n_wesolowski_with_b_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            b.as_ptr(),
+            b.len(),
+            x_s.as_ptr(),
+            x_s.len(),
+            proof.as_ptr(),
+            proof.len(),
+            num_iterations,
+            recursion,
+            y_s.as_mut_ptr(),
+            y_s.len(),
+        )
+    };
+    match last_error() {
+        Some(error) if !valid => Err(error),
+        _ => Ok(valid.then_some(y_s)),
+    }
+}
+
+/// Return the challenge B of the output of a valid N-Wesolowski proof, which replaces the output
+/// in the compact format of [verify_n_wesolowski_with_b]. Fails if the blob has the wrong size for
+/// `recursion` or one of its segments is invalid.
+pub fn get_b_from_n_wesolowski(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    proof: &[u8],
+    num_iterations: u64,
+    recursion: u64,
+) -> ChiavdfResult<Vec<u8>> {
+    check_form_size(discriminant, x_s)?;
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
+    unsafe {
+        let array = bindings::get_b_from_n_wesolowski_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            x_s.as_ptr(),
+            x_s.len(),
+            proof.as_ptr(),
+            proof.len(),
+            num_iterations,
+            recursion,
+        );
+        take_byte_array(array)
+    }
+}
+
+/// A proof to check with [verify_parallel]. Each job has its own discriminant.
+#[derive(Clone, Copy, Debug)]
+pub enum VerifyJob<'a> {
+    /// The arguments of [verify].
+    Wesolowski {
+        discriminant: &'a Discriminant,
+        x_s: &'a [u8],
+        y_s: &'a [u8],
+        proof: &'a [u8],
+        num_iterations: u64,
+    },
+    /// The arguments of [verify_n_wesolowski].
+    NWesolowski {
+        discriminant: &'a Discriminant,
+        x_s: &'a [u8],
+        proof: &'a [u8],
+        num_iterations: u64,
+        recursion: u64,
+    },
+}
+
+impl VerifyJob<'_> {
+    /// Verify the proof on the calling thread.
+    pub fn verify(&self) -> ChiavdfResult<bool> {
+        match *self {
+            VerifyJob::Wesolowski {
+                discriminant,
+                x_s,
+                y_s,
+                proof,
+                num_iterations,
+            } => verify(discriminant, x_s, y_s, proof, num_iterations),
+            VerifyJob::NWesolowski {
+                discriminant,
+                x_s,
+                proof,
+                num_iterations,
+                recursion,
+            } => verify_n_wesolowski(discriminant, x_s, proof, num_iterations, recursion),
+        }
+    }
+}
+
+/// Verify the jobs on at most `threads` threads, or one per core if `threads` is 0, and return the
+/// result of each job in order. The C++ verifier keeps its state on the stack of each call and
+/// records errors per thread, so jobs don't interfere with each other.
+pub fn verify_parallel(jobs: &[VerifyJob], threads: usize) -> Vec<ChiavdfResult<bool>> {
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        threads => threads,
+    };
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<ChiavdfResult<bool>>> = vec![None; jobs.len()];
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads.min(jobs.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(job) = jobs.get(i) else {
+                            return done;
+                        };
+                        done.push((i, job.verify()));
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            let done = worker
+                .join()
+                .unwrap_or_else(|payload| panic::resume_unwind(payload));
+            for (i, result) in done {
+                results[i] = Some(result);
+            }
+        }
+    });
+    results
+        .into_iter()
+        .map(|result| result.expect("every job is verified"))
+        .collect()
+}
+
+pub fn from_ab(discriminant: &Discriminant, a: &[u8], b: &[u8]) -> ChiavdfResult<Vec<u8>> {
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
     unsafe {
         let array = bindings::from_ab(
-            discriminant.as_ptr(),
-            discriminant.len(),
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
             a.as_ptr(),
             a.len(),
             b.as_ptr(),
             b.len(),
         );
-        if array.data.is_null() {
-            return None;
-        }
-        let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        Some(result)
+        take_byte_array(array)
+    }
+}
+
+/// Build the form (a, b, c) of the class group of `discriminant`. The coefficients must satisfy
+/// b² - 4ac = D and the form must be reduced.
+pub fn from_abc(
+    discriminant: &Discriminant,
+    a: &BigInt,
+    b: &BigInt,
+    c: &BigInt,
+) -> ChiavdfResult<Vec<u8>> {
+    if !a.is_positive() {
+        return Err(ChiavdfError::InvalidInput("a must be positive".to_string()));
+    }
+    if b * b - 4 * a * c != discriminant.to_bigint() {
+        return Err(ChiavdfError::InvalidInput(
+            "b² - 4ac does not match the discriminant".to_string(),
+        ));
     }
+    let a = a.magnitude().to_bytes_be();
+    let b_negative = b.is_negative();
+    let b = b.magnitude().to_bytes_be();
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
+    unsafe {
+        let array = bindings::from_ab_reduced(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            a.as_ptr(),
+            a.len(),
+            b.as_ptr(),
+            b.len(),
+            b_negative,
+        );
+        take_byte_array(array)
+    }
+}
+
+/// Return the coefficients (a, b, c) of a form of the class group of `discriminant`.
+pub fn decompress(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+) -> ChiavdfResult<(BigInt, BigInt, BigInt)> {
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
+    let result = unsafe {
+        let array = bindings::decompress_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            x_s.as_ptr(),
+            x_s.len(),
+        );
+        take_byte_array(array)?
+    };
+    // The sign of b followed by |a|, |b| and |c| with the byte length of the discriminant
+    let size = discriminant.as_bytes().len();
+    let coefficient = |i: usize| &result[1 + i * size..1 + (i + 1) * size];
+    let b_sign = if result[0] == 1 {
+        Sign::Minus
+    } else {
+        Sign::Plus
+    };
+    Ok((
+        BigInt::from_bytes_be(Sign::Plus, coefficient(0)),
+        BigInt::from_bytes_be(b_sign, coefficient(1)),
+        BigInt::from_bytes_be(Sign::Plus, coefficient(2)),
+    ))
 }
 
-pub fn identity(discriminant: &[u8]) -> Option<Vec<u8>> {
+pub fn identity(discriminant: &Discriminant) -> ChiavdfResult<Vec<u8>> {
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
     unsafe {
-        let array = bindings::identity_wrapper(discriminant.as_ptr(), discriminant.len());
-        if array.data.is_null() {
-            return None;
-        }
-        let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        Some(result)
+        let array = bindings::identity_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+        );
+        take_byte_array(array)
     }
 }
 
-pub fn generator(discriminant: &[u8]) -> Option<Vec<u8>> {
+pub fn generator(discriminant: &Discriminant) -> ChiavdfResult<Vec<u8>> {
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
     unsafe {
-        let array = bindings::generator_wrapper(discriminant.as_ptr(), discriminant.len());
-        if array.data.is_null() {
-            return None;
-        }
-        let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        Some(result)
+        let array = bindings::generator_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+        );
+        take_byte_array(array)
     }
 }
 
-pub fn power(discriminant: &[u8], x_s: &[u8], power: &[u8]) -> Option<Vec<u8>> {
+pub fn power(discriminant: &Discriminant, x_s: &[u8], power: &[u8]) -> ChiavdfResult<Vec<u8>> {
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
     unsafe {
         let array = bindings::power_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
             x_s.as_ptr(),
             x_s.len(),
             power.as_ptr(),
             power.len(),
         );
-        if array.data.is_null() {
-            return None;
-        }
-        let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        Some(result)
+        take_byte_array(array)
     }
 }
 
-pub fn multiply(discriminant: &[u8], x_s: &[u8], y_s: &[u8]) -> Option<Vec<u8>> {
+pub fn multiply(discriminant: &Discriminant, x_s: &[u8], y_s: &[u8]) -> ChiavdfResult<Vec<u8>> {
+    check_form_sizes(x_s, &[y_s])?;
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and a null pointer is returned for `data` if so.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
     unsafe {
         let array = bindings::multiply_wrapper(
-            discriminant.as_ptr(),
-            discriminant.len(),
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
             x_s.as_ptr(),
             y_s.as_ptr(),
             x_s.len(),
         );
-        if array.data.is_null() {
-            return None;
-        }
-        let result = std::slice::from_raw_parts(array.data, array.length).to_vec();
-        bindings::delete_byte_array(array);
-        Some(result)
+        take_byte_array(array)
     }
 }
 
-pub fn hash_int(seed: &[u8], result: &mut [u8]) -> bool {
+/// Raise `x_s` to the power `exponent`, given in big-endian bytes, like [power], and prove it with
+/// a Wesolowski proof of exponentiation. For the prime l derived from the discriminant, x, y and
+/// the exponent with `HashPrime`, the proof is x^floor(exponent / l). Return y followed by the
+/// proof.
+///
+/// The exponent must be longer than the [B_BITS] bits of l. Below that the proof is the identity
+/// and checking it costs more than raising x to the exponent again with [power].
+pub fn prove_exponentiation(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    exponent: &[u8],
+) -> ChiavdfResult<(Vec<u8>, Vec<u8>)> {
+    check_form_size(discriminant, x_s)?;
+    let exponent_int = exponentiation_exponent(exponent)?;
+    let y_s = power(discriminant, x_s, exponent)?;
+    let l = exponentiation_challenge(discriminant, x_s, &y_s, &exponent_int)?;
+    let proof = power(discriminant, x_s, &(exponent_int / l).to_bytes_be())?;
+    Ok((y_s, proof))
+}
+
+/// Verify that `y_s` is `x_s` raised to the power `exponent` with a proof from
+/// [prove_exponentiation], by checking that proof^l * x^(exponent mod l) is y. This costs two
+/// exponentiations by integers of at most [B_BITS] bits, whatever the size of the exponent, which
+/// must be longer than that as well.
+pub fn verify_exponentiation(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    exponent: &[u8],
+    proof: &[u8],
+) -> ChiavdfResult<bool> {
+    for form in [x_s, y_s, proof] {
+        check_form_size(discriminant, form)?;
+    }
+    let exponent = exponentiation_exponent(exponent)?;
+    let l = exponentiation_challenge(discriminant, x_s, y_s, &exponent)?;
+    let proof_l = power(discriminant, proof, &l.to_bytes_be())?;
+    let x_r = power(discriminant, x_s, &(exponent % l).to_bytes_be())?;
+    Ok(multiply(discriminant, &proof_l, &x_r)? == y_s)
+}
+
+// The exponent of a proof of exponentiation, which must be longer than the challenge prime
+fn exponentiation_exponent(exponent: &[u8]) -> ChiavdfResult<BigUint> {
+    let exponent = BigUint::from_bytes_be(exponent);
+    if exponent.bits() <= B_BITS as u64 {
+        return Err(ChiavdfError::InvalidInput(format!(
+            "Exponents of at most {B_BITS} bits are cheaper to recompute than to prove"
+        )));
+    }
+    Ok(exponent)
+}
+
+// The prime l of a proof of exponentiation, derived like the B of the Wesolowski proofs. Any
+// discriminant can be given, so it is part of the seed, and the lengths keep the parts apart.
+fn exponentiation_challenge(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    exponent: &BigUint,
+) -> ChiavdfResult<BigUint> {
+    let discriminant = discriminant.as_bytes();
+    let exponent = exponent.to_bytes_be();
+    let seed = [
+        &(discriminant.len() as u64).to_be_bytes(),
+        discriminant,
+        x_s,
+        y_s,
+        &(exponent.len() as u64).to_be_bytes(),
+        &exponent,
+    ]
+    .concat();
+    let mut l = [0; B_BYTES];
+    hash_prime(&seed, &mut l)?;
+    Ok(BigUint::from_bytes_be(&l))
+}
+
+pub fn hash_int(seed: &[u8], result: &mut [u8]) -> ChiavdfResult<()> {
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and None is returned if so.
-    unsafe {
+    // Exceptions are caught on the C++ side, which returns false and records the error.
+    let success = unsafe {
         bindings::hash_int_wrapper(
             seed.as_ptr(),
             seed.len(),
             result.len() * 8,
             result.as_mut_ptr(),
         )
+    };
+    if !success {
+        return Err(failure());
     }
+    Ok(())
 }
 
-pub fn hash_prime(seed: &[u8], result: &mut [u8]) -> bool {
+pub fn hash_prime(seed: &[u8], result: &mut [u8]) -> ChiavdfResult<()> {
     // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
-    // Exceptions are handled on the C++ side and None is returned if so.
-    unsafe {
+    // Exceptions are caught on the C++ side, which returns false and records the error.
+    let success = unsafe {
         bindings::hash_prime_wrapper(
             seed.as_ptr(),
             seed.len(),
             result.len() * 8,
             result.as_mut_ptr(),
         )
+    };
+    if !success {
+        return Err(failure());
     }
+    Ok(())
+}
+
+/// The Fiat-Shamir challenge of a proof from `x_s` to `y_s`: the 264-bit prime B of the Wesolowski
+/// proof, `GetB` on the C++ side, as [B_BYTES] big-endian bytes.
+pub fn challenge_prime(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+) -> ChiavdfResult<Vec<u8>> {
+    check_form_sizes(x_s, &[y_s])?;
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
+    unsafe {
+        let array = bindings::challenge_prime_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            x_s.as_ptr(),
+            y_s.as_ptr(),
+            x_s.len(),
+        );
+        take_byte_array(array)
+    }
+}
+
+/// Hash `x_s` and `y_s` to an integer of `bits` bits, a multiple of 8 of at most 256, like
+/// [hash_int] on the concatenation of their serializations. Returns `bits / 8` big-endian bytes.
+pub fn challenge_int(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    bits: usize,
+) -> ChiavdfResult<Vec<u8>> {
+    if bits == 0 || !bits.is_multiple_of(8) || bits > 256 {
+        return Err(ChiavdfError::InvalidInput(
+            "The length must be a multiple of 8 of at most 256 bits".to_string(),
+        ));
+    }
+    check_form_sizes(x_s, &[y_s])?;
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
+    let value = unsafe {
+        let array = bindings::challenge_int_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            x_s.as_ptr(),
+            y_s.as_ptr(),
+            x_s.len(),
+            bits,
+        );
+        take_byte_array(array)?
+    };
+    // The C++ side drops the leading zero bytes
+    let mut result = vec![0; bits / 8 - value.len()];
+    result.extend(value);
+    Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
     use hex_literal::hex;
 
+    use sha2::{Digest, Sha256};
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
     use super::*;
+    use crate::constants::FORM_SIZE;
 
     #[test]
     fn test_create_discriminant() {
@@ -326,7 +1420,7 @@ mod tests {
 
         for seed in seeds {
             let mut discriminant = [0; 64];
-            assert!(create_discriminant(&seed, &mut discriminant));
+            assert!(create_discriminant(&seed, &mut discriminant).is_ok());
             discriminants.push(discriminant);
         }
 
@@ -355,28 +1449,26 @@ mod tests {
         let genesis_challenge =
             hex!("ccd5bb71183532bff220ba46c268991a3ff07eb358e8255a65c30a2dce0e5fbb");
 
-        let mut default_el = [0; FORM_SIZE];
-        default_el[0] = 0x08;
-
         const discriminant_size: usize = 4096;
-        assert!(discriminant_size & 8 == 0);
-        let mut disc = [0; discriminant_size / 8];
-        assert!(create_discriminant(&genesis_challenge, &mut disc));
+        let disc = Discriminant::from_seed(&genesis_challenge, discriminant_size).unwrap();
+
+        let mut default_el = vec![0; disc.form_size()];
+        default_el[0] = 0x08;
 
         let num_iterations = 231;
 
         // Check that `evaluate_and_prove` verifies successfully with `verify`
         let (result, proof) = evaluate_and_prove(&disc, &default_el, num_iterations).unwrap();
-        let valid = verify(&disc, &default_el, &result, &proof, num_iterations);
+        let valid = verify(&disc, &default_el, &result, &proof, num_iterations).unwrap();
         assert!(valid);
 
         // Negative test on verify
-        let valid = verify(&disc, &result, &result, &proof, num_iterations);
+        let valid = verify(&disc, &result, &result, &proof, num_iterations).unwrap();
         assert!(!valid);
 
         // Verify that `prove` verifies successfully
         let proof2 = prove(&disc, &default_el, &result, num_iterations).unwrap();
-        let valid = verify(&disc, &default_el, &result, &proof2, num_iterations);
+        let valid = verify(&disc, &default_el, &result, &proof2, num_iterations).unwrap();
         assert!(valid);
 
         // Verify that `evaluate_to_prove` and `prove_ext` verifies successfully
@@ -384,17 +1476,784 @@ mod tests {
             evaluate_to_prove(&disc, &default_el, num_iterations).unwrap();
         let proof3 =
             prove_ext(&disc, &default_el, &result3, &intermediates, num_iterations).unwrap();
-        let valid = verify(&disc, &default_el, &result3, &proof3, num_iterations);
+        let valid = verify(&disc, &default_el, &result3, &proof3, num_iterations).unwrap();
         assert!(valid);
 
         // Verifying that the different representation of evaluation and proofs work together
-        let valid = verify(&disc, &default_el, &result, &proof3, num_iterations);
+        let valid = verify(&disc, &default_el, &result, &proof3, num_iterations).unwrap();
         assert!(valid);
 
-        let valid = verify(&disc, &default_el, &result3, &proof, num_iterations);
+        let valid = verify(&disc, &default_el, &result3, &proof, num_iterations).unwrap();
         assert!(valid);
 
-        let valid = verify(&disc, &default_el, &result3, &proof2, num_iterations);
+        let valid = verify(&disc, &default_el, &result3, &proof2, num_iterations).unwrap();
         assert!(valid);
     }
+
+    #[test]
+    fn test_form_sizes() {
+        for (d_bits, form_size) in [(512, 52), (1000, 100), (1024, 100), (2048, 196)] {
+            let disc = Discriminant::from_seed(b"HelloWorld", d_bits).unwrap();
+            assert_eq!(disc.form_size(), form_size);
+            let x = generator(&disc).unwrap();
+            assert_eq!(x.len(), form_size);
+
+            let num_iterations = 300;
+            let (y, proof) = evaluate_and_prove(&disc, &x, num_iterations).unwrap();
+            assert_eq!((y.len(), proof.len()), (form_size, form_size));
+            assert_eq!(verify(&disc, &x, &y, &proof, num_iterations), Ok(true));
+
+            let (y2, intermediates) = evaluate_to_prove(&disc, &x, num_iterations).unwrap();
+            assert_eq!(y2, y);
+            assert!(intermediates.len().is_multiple_of(form_size));
+            let proof2 = prove_ext(&disc, &x, &y, &intermediates, num_iterations).unwrap();
+            assert_eq!(verify(&disc, &x, &y, &proof2, num_iterations), Ok(true));
+
+            let blob = [y.clone(), proof].concat();
+            assert_eq!(
+                verify_n_wesolowski(&disc, &x, &blob, num_iterations, 0),
+                Ok(true)
+            );
+
+            // Forms of the largest discriminants are not accepted for smaller ones
+            let mut padded = x.clone();
+            padded.resize(FORM_SIZE, 0);
+            assert!(verify(&disc, &padded, &padded, &padded, num_iterations).is_err());
+            assert!(prove_ext(&disc, &x, &y, &intermediates[1..], num_iterations).is_err());
+        }
+    }
+
+    #[test]
+    fn test_fixed_encoding() {
+        // Forms, proofs and challenges of 1024-bit discriminants take 100 bytes per form, not the
+        // 388 bytes of the largest discriminants
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let y = hex!("000015d2e79a4671fa80bb10220015a05e8e533fc79f7d84ea77a783b8188c8d992e5cebc1259329a60cd9e8f4bacdeee21990028b096218154ca97d07b889e33d49cb8b16420466f8ca507e3cc224ee78ff57e7c2c67a0f0d84d72f0ee6643cf0410100");
+        let proof = hex!("00009433dca364004db9adec1cda3f616036edca5361c8af001f141f7a46f0c7c3029ce48c101ed0456ccbd376b733f34cf15c91b5559a84f2d6facb13d4c47ec119133da6f606a93880b0532080e563cb13387a7fbda143a28ddba517ff2f1a092b0300");
+
+        assert_eq!(
+            evaluate_and_prove(&disc, &x, 300).unwrap(),
+            (y.to_vec(), proof.to_vec())
+        );
+        assert_eq!(
+            challenge_prime(&disc, &x, &y).unwrap(),
+            hex!("864d946cbc895f21d4738354a2e135a554e6cef0208f5d3c2347ab114081456c19")
+        );
+        assert_eq!(verify(&disc, &x, &y, &proof, 300), Ok(true));
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+
+        // An unset flag does not change the results
+        let cancel = Arc::new(AtomicBool::new(false));
+        assert_eq!(
+            evaluate_and_prove_cancellable(&disc, &x, 1000, &cancel),
+            evaluate_and_prove(&disc, &x, 1000)
+        );
+        assert_eq!(
+            evaluate_to_prove_cancellable(&disc, &x, 1000, &cancel),
+            evaluate_to_prove(&disc, &x, 1000)
+        );
+        assert_eq!(
+            n_prove_cancellable(&disc, &x, 1000, &cancel),
+            n_prove(&disc, &x, 1000)
+        );
+        let y = evaluate(&disc, &x, 1000).unwrap();
+        assert_eq!(
+            prove_cancellable(&disc, &x, &y, 1000, &cancel),
+            prove(&disc, &x, &y, 1000)
+        );
+
+        // A set flag stops the evaluation right away. The intermediates of the full evaluation
+        // are allocated first, so the number of iterations must stay reasonable.
+        let num_iterations = 1 << 22;
+        cancel.store(true, Ordering::Relaxed);
+        assert_eq!(
+            evaluate_and_prove_cancellable(&disc, &x, num_iterations, &cancel),
+            Err(ChiavdfError::Cancelled)
+        );
+        assert_eq!(
+            evaluate_to_prove_cancellable(&disc, &x, num_iterations, &cancel),
+            Err(ChiavdfError::Cancelled)
+        );
+        assert_eq!(
+            prove_cancellable(&disc, &x, &y, num_iterations, &cancel),
+            Err(ChiavdfError::Cancelled)
+        );
+
+        // Cancelling from another thread
+        let cancel = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let cancel = cancel.clone();
+            let x = x.clone();
+            let disc = disc.clone();
+            std::thread::spawn(move || n_prove_cancellable(&disc, &x, num_iterations, &cancel))
+        };
+        std::thread::sleep(Duration::from_millis(100));
+        cancel.store(true, Ordering::Relaxed);
+        assert_eq!(handle.join().unwrap(), Err(ChiavdfError::Cancelled));
+
+        // The next call is not affected
+        let (y, proof) = evaluate_and_prove(&disc, &x, 100).unwrap();
+        assert_eq!(verify(&disc, &x, &y, &proof, 100), Ok(true));
+    }
+
+    #[test]
+    fn test_progress() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+
+        let mut reports = Vec::new();
+        let result = evaluate_and_prove_with_progress(&disc, &x, 10_000, 1000, None, |progress| {
+            reports.push(progress)
+        });
+        assert_eq!(result, evaluate_and_prove(&disc, &x, 10_000));
+        let iterations: Vec<u64> = reports.iter().map(|progress| progress.iterations).collect();
+        assert_eq!(iterations, (1..=10).map(|i| i * 1000).collect::<Vec<_>>());
+        assert!(reports.windows(2).all(|w| w[0].elapsed <= w[1].elapsed));
+        assert!(reports
+            .iter()
+            .all(|progress| progress.iterations_per_second > 0.0));
+
+        // The callback can cancel the evaluation
+        let cancel = AtomicBool::new(false);
+        let mut count = 0;
+        let result = evaluate_to_prove_with_progress(&disc, &x, 10_000, 100, Some(&cancel), |_| {
+            count += 1;
+            if count == 3 {
+                cancel.store(true, Ordering::Relaxed);
+            }
+        });
+        assert_eq!(result, Err(ChiavdfError::Cancelled));
+        assert_eq!(count, 3);
+
+        // No reports with a zero interval
+        let result = evaluate_to_prove_with_progress(&disc, &x, 1000, 0, None, |_| panic!());
+        assert_eq!(result, evaluate_to_prove(&disc, &x, 1000));
+
+        // A panic in the callback stops the evaluation and reaches the caller
+        let result = panic::catch_unwind(|| {
+            evaluate_to_prove_with_progress(&disc, &x, 1 << 22, 100, None, |_| panic!("stop"))
+        });
+        assert_eq!(*result.unwrap_err().downcast::<&str>().unwrap(), "stop");
+    }
+
+    #[test]
+    fn test_checkpoints() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let num_iterations = 10_000;
+        let (y, intermediates) = evaluate_to_prove(&disc, &x, num_iterations).unwrap();
+
+        let mut checkpoints = Vec::new();
+        let result = evaluate_to_prove_with_checkpoints(
+            &disc,
+            &x,
+            num_iterations,
+            3000,
+            None,
+            |checkpoint| checkpoints.push(checkpoint.clone()),
+        );
+        assert_eq!(result, Ok((y.clone(), intermediates.clone())));
+        let iterations: Vec<u64> = checkpoints.iter().map(|c| c.iterations).collect();
+        assert_eq!(iterations, [3000, 6000, 9000]);
+        let last = checkpoints.last().unwrap();
+        assert_eq!(last.x, x);
+        assert!(intermediates.starts_with(&last.intermediates));
+
+        // Stop after a checkpoint and resume from its serialization
+        let cancel = AtomicBool::new(false);
+        let mut saved = None;
+        let result = evaluate_to_prove_with_checkpoints(
+            &disc,
+            &x,
+            num_iterations,
+            4000,
+            Some(&cancel),
+            |checkpoint| {
+                saved = Some(checkpoint.to_bytes());
+                cancel.store(true, Ordering::Relaxed);
+            },
+        );
+        assert_eq!(result, Err(ChiavdfError::Cancelled));
+        let checkpoint = Checkpoint::from_bytes(&saved.unwrap()).unwrap();
+        assert_eq!(checkpoint.iterations, 4000);
+
+        let (resumed_y, resumed_intermediates) = resume_evaluation(&disc, &checkpoint).unwrap();
+        assert_eq!(resumed_y, y);
+        assert_eq!(resumed_intermediates, intermediates);
+        let proof = prove_ext(&disc, &x, &y, &intermediates, num_iterations).unwrap();
+        assert_eq!(
+            prove_ext(
+                &disc,
+                &x,
+                &resumed_y,
+                &resumed_intermediates,
+                num_iterations
+            ),
+            Ok(proof.clone())
+        );
+        assert_eq!(verify(&disc, &x, &y, &proof, num_iterations), Ok(true));
+
+        // Resuming keeps checkpointing from the checkpoint
+        let mut iterations = Vec::new();
+        let result = resume_evaluation_with_checkpoints(&disc, &checkpoint, 5000, None, |c| {
+            iterations.push(c.iterations);
+            assert!(c.intermediates.starts_with(&checkpoint.intermediates));
+        });
+        assert_eq!(result, Ok((y, intermediates)));
+        assert_eq!(iterations, [5000, 10_000]);
+
+        // Checkpoints which do not match the evaluation are rejected
+        let other = Discriminant::from_seed(b"Other", 512).unwrap();
+        assert!(resume_evaluation(&other, &checkpoint).is_err());
+        let mut wrong = checkpoint.clone();
+        wrong.num_iterations = 1 << 30;
+        assert!(resume_evaluation(&disc, &wrong).is_err());
+        let mut wrong = checkpoint.clone();
+        wrong.intermediates.truncate(disc.form_size());
+        assert!(resume_evaluation(&disc, &wrong).is_err());
+        let mut wrong = checkpoint.clone();
+        wrong.iterations = num_iterations + 1;
+        assert!(resume_evaluation(&disc, &wrong).is_err());
+        assert!(Checkpoint::from_bytes(&checkpoint.to_bytes()[..100]).is_err());
+    }
+
+    /// The N-Wesolowski blob `prove_n_wesolowski` makes, built from a Wesolowski proof of each of
+    /// its segments
+    fn n_wesolowski_blob(
+        disc: &Discriminant,
+        x: &[u8],
+        num_iterations: u64,
+        recursion: u64,
+    ) -> Vec<u8> {
+        let mut x = x.to_vec();
+        let mut remaining = num_iterations;
+        let mut segments = Vec::new();
+        for _ in 0..recursion {
+            let iterations = remaining * 2 / 3 / 100 * 100;
+            let (y, proof) = evaluate_and_prove(disc, &x, iterations).unwrap();
+            let b = challenge_prime(disc, &x, &y).unwrap();
+            segments.push([&iterations.to_be_bytes()[..], &b, &proof].concat());
+            x = y;
+            remaining -= iterations;
+        }
+        let (y, proof) = evaluate_and_prove(disc, &x, remaining).unwrap();
+        let mut blob = [y, proof].concat();
+        for segment in segments.iter().rev() {
+            blob.extend_from_slice(segment);
+        }
+        blob
+    }
+
+    #[cfg(feature = "timelord")]
+    #[test]
+    fn test_n_wesolowski() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let num_iterations = 10_000;
+        let segment_size = 8 + 33 + disc.form_size();
+
+        for recursion in 0..4 {
+            let blob = prove_n_wesolowski(&disc, &x, num_iterations, recursion).unwrap();
+            assert_eq!(
+                blob.len(),
+                2 * disc.form_size() + recursion as usize * segment_size
+            );
+            assert_eq!(
+                blob,
+                n_wesolowski_blob(&disc, &x, num_iterations, recursion)
+            );
+            assert_eq!(
+                verify_n_wesolowski(&disc, &x, &blob, num_iterations, recursion),
+                Ok(true)
+            );
+            assert_eq!(
+                verify_n_wesolowski(&disc, &x, &blob, num_iterations + 1, recursion),
+                Ok(false)
+            );
+            assert_eq!(
+                verify_n_wesolowski(&disc, &x, &blob, num_iterations, recursion + 1),
+                Ok(false)
+            );
+        }
+
+        // Each extra segment needs at least 100 squarings
+        assert!(prove_n_wesolowski(&disc, &x, 200, 1).is_ok());
+        assert!(prove_n_wesolowski(&disc, &x, 200, 2).is_err());
+        assert!(matches!(
+            prove_n_wesolowski(&disc, &x[1..], 200, 1),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+    }
+
+    #[cfg(feature = "timelord")]
+    #[test]
+    fn test_prover_session() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let session = ProverSession::start(&disc, &x).unwrap();
+
+        // Within the first segment of 2^16 squarings, past it, and past three of them
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = [(1000, 0), (70_000, 1), (200_000, 3)]
+                .into_iter()
+                .map(|(num_iterations, recursion)| {
+                    let session = &session;
+                    scope.spawn(move || (num_iterations, recursion, session.prove(num_iterations)))
+                })
+                .collect();
+            for handle in handles {
+                let (num_iterations, recursion, result) = handle.join().unwrap();
+                let (y, proof, witness_type) = result.unwrap();
+                assert_eq!(witness_type, recursion);
+                let blob = [y, proof].concat();
+                assert_eq!(
+                    verify_n_wesolowski(&disc, &x, &blob, num_iterations, witness_type),
+                    Ok(true)
+                );
+            }
+        });
+        assert!(session.iterations() >= 200_000);
+        assert!(matches!(
+            session.prove(0),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+        assert!(session.prove(800_000_000).is_err());
+
+        // Stopping cancels the pending proofs and the later ones
+        std::thread::scope(|scope| {
+            let pending = scope.spawn(|| session.prove(100_000_000));
+            session.stop().unwrap();
+            assert_eq!(pending.join().unwrap(), Err(ChiavdfError::Cancelled));
+        });
+        let done = session.iterations();
+        assert_eq!(session.prove(1000), Err(ChiavdfError::Cancelled));
+        session.stop().unwrap();
+        assert_eq!(session.iterations(), done);
+
+        assert!(ProverSession::start(&disc, &x[1..]).is_err());
+    }
+
+    #[test]
+    fn test_n_wesolowski_with_b() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let num_iterations = 1000;
+        let form_size = disc.form_size();
+
+        for recursion in 0..3 {
+            let blob = n_wesolowski_blob(&disc, &x, num_iterations, recursion);
+            let b = get_b_from_n_wesolowski(&disc, &x, &blob, num_iterations, recursion).unwrap();
+            assert_eq!(b.len(), B_BYTES);
+            let compact = &blob[form_size..];
+            assert_eq!(
+                verify_n_wesolowski_with_b(&disc, &b, &x, compact, num_iterations, recursion),
+                Ok(Some(blob[..form_size].to_vec()))
+            );
+
+            // Wrong iterations, B or sizes make the proof invalid
+            let mut wrong_b = b.clone();
+            wrong_b[10] ^= 1;
+            for (b, compact, num_iterations, recursion) in [
+                (&b, compact, num_iterations + 1, recursion),
+                (&wrong_b, compact, num_iterations, recursion),
+                (&b, &blob[..], num_iterations, recursion),
+                (&b, compact, num_iterations, recursion + 1),
+                (&b, compact, num_iterations, u64::MAX),
+            ] {
+                assert_eq!(
+                    verify_n_wesolowski_with_b(&disc, b, &x, compact, num_iterations, recursion),
+                    Ok(None)
+                );
+            }
+
+            assert!(
+                get_b_from_n_wesolowski(&disc, &x, compact, num_iterations, recursion).is_err()
+            );
+            assert!(get_b_from_n_wesolowski(&disc, &x, &blob, num_iterations, u64::MAX).is_err());
+        }
+
+        let blob = n_wesolowski_blob(&disc, &x, num_iterations, 1);
+        assert!(matches!(
+            get_b_from_n_wesolowski(&disc, &x[1..], &blob, num_iterations, 1),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            verify_n_wesolowski_with_b(&disc, &[1], &x[1..], &blob, num_iterations, 1),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_discriminant_and_verify_n_wesolowski() {
+        let challenge = b"HelloWorld";
+        let disc = Discriminant::from_seed(challenge, 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let blob = n_wesolowski_blob(&disc, &x, 1000, 2);
+
+        for (num_iterations, recursion) in [(1000, 2), (1001, 2), (1000, 1), (1000, u64::MAX)] {
+            assert_eq!(
+                create_discriminant_and_verify_n_wesolowski(
+                    challenge,
+                    1024,
+                    &x,
+                    &blob,
+                    num_iterations,
+                    recursion
+                ),
+                verify_n_wesolowski(&disc, &x, &blob, num_iterations, recursion)
+            );
+        }
+        // x is not a form of the discriminant of another challenge
+        assert!(matches!(
+            create_discriminant_and_verify_n_wesolowski(b"Other", 1024, &x, &blob, 1000, 2),
+            Err(ChiavdfError::InvalidForm(_))
+        ));
+        assert_eq!(
+            create_discriminant_and_verify_n_wesolowski(challenge, 1024, &x, &blob[1..], 1000, 2),
+            Ok(false)
+        );
+
+        // The form size must follow the discriminant size
+        for size_bits in [0, 512, 8192] {
+            assert!(matches!(
+                create_discriminant_and_verify_n_wesolowski(
+                    challenge, size_bits, &x, &blob, 1000, 2
+                ),
+                Err(ChiavdfError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_challenges() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let (y, proof) = evaluate_and_prove(&disc, &x, 1000).unwrap();
+
+        // B is the challenge the verifier recovers from a proof with no extra segments
+        let b = challenge_prime(&disc, &x, &y).unwrap();
+        assert_eq!(b.len(), B_BYTES);
+        let blob = [y.clone(), proof].concat();
+        assert_eq!(
+            get_b_from_n_wesolowski(&disc, &x, &blob, 1000, 0),
+            Ok(b.clone())
+        );
+        assert_ne!(challenge_prime(&disc, &y, &x).unwrap(), b);
+
+        let digest = Sha256::digest([x.clone(), y.clone()].concat());
+        for bits in [8, 64, 256] {
+            assert_eq!(
+                challenge_int(&disc, &x, &y, bits).unwrap(),
+                digest[..bits / 8]
+            );
+        }
+        for bits in [0, 12, 264] {
+            assert!(matches!(
+                challenge_int(&disc, &x, &y, bits),
+                Err(ChiavdfError::InvalidInput(_))
+            ));
+        }
+        assert!(challenge_prime(&disc, &x, &y[1..]).is_err());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+
+        for num_iterations in [0, 1, 1000] {
+            let y = evaluate(&disc, &x, num_iterations).unwrap();
+            assert_eq!(y, evaluate_and_prove(&disc, &x, num_iterations).unwrap().0);
+            let proof = prove(&disc, &x, &y, num_iterations).unwrap();
+            assert_eq!(verify(&disc, &x, &y, &proof, num_iterations), Ok(true));
+        }
+        assert!(evaluate(&disc, &x[1..], 1000).is_err());
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let g = generator(&disc).unwrap();
+        let proofs: Vec<_> = (1u64..=6)
+            .map(|i| {
+                let x = power(&disc, &g, &i.to_be_bytes()).unwrap();
+                let num_iterations = 100 * i;
+                let (y, proof) = evaluate_and_prove(&disc, &x, num_iterations).unwrap();
+                (x, y, proof, num_iterations)
+            })
+            .collect();
+        type Owned = (Vec<u8>, Vec<u8>, Vec<u8>, u64);
+        let batch = |proofs: &[Owned]| {
+            let proofs: Vec<_> = proofs
+                .iter()
+                .map(|(x, y, proof, t)| (&x[..], &y[..], &proof[..], *t))
+                .collect();
+            verify_batch(&disc, &proofs)
+        };
+
+        assert_eq!(batch(&proofs), vec![Ok(true); 6]);
+        assert!(batch(&[]).is_empty());
+
+        // The invalid proofs are pinpointed
+        let mut invalid = proofs.clone();
+        invalid[1].3 += 1;
+        invalid[4].2 = invalid[3].2.clone();
+        let mut corrupted = invalid[5].1.clone();
+        corrupted[20] ^= 1;
+        invalid[5].1 = corrupted;
+        let results = batch(&invalid);
+        assert_eq!(
+            results[..5],
+            [Ok(true), Ok(false), Ok(true), Ok(true), Ok(false)]
+        );
+        assert!(matches!(results[5], Err(ChiavdfError::InvalidForm(_))));
+
+        // Swapping the outputs of two proofs is caught as well
+        let mut swapped = proofs.clone();
+        swapped[0].1 = proofs[1].1.clone();
+        swapped[1].1 = proofs[0].1.clone();
+        assert_eq!(batch(&swapped)[..2], [Ok(false), Ok(false)]);
+
+        let mut short = proofs.clone();
+        short[0].2.pop();
+        assert!(batch(&short)[0].is_err());
+    }
+
+    #[test]
+    fn test_verify_parallel() {
+        let discs: Vec<_> = (0u8..3)
+            .map(|i| Discriminant::from_seed(&[i; 10], 512 + 256 * i as usize).unwrap())
+            .collect();
+        let num_iterations = 1000;
+        let proofs: Vec<_> = discs
+            .iter()
+            .map(|disc| {
+                let x = generator(disc).unwrap();
+                let (y, proof) = evaluate_and_prove(disc, &x, num_iterations).unwrap();
+                let blob = n_wesolowski_blob(disc, &x, num_iterations, 2);
+                let mut corrupted = y.clone();
+                corrupted[20] ^= 1;
+                (x, y, proof, blob, corrupted)
+            })
+            .collect();
+
+        // Valid, invalid and undecodable proofs of every discriminant, interleaved so that each
+        // thread sees all of them
+        let mut jobs = Vec::new();
+        for _ in 0..10 {
+            for (disc, (x, y, proof, blob, corrupted)) in discs.iter().zip(&proofs) {
+                for (y_s, t) in [
+                    (y, num_iterations),
+                    (y, num_iterations + 1),
+                    (corrupted, num_iterations),
+                ] {
+                    jobs.push(VerifyJob::Wesolowski {
+                        discriminant: disc,
+                        x_s: x,
+                        y_s,
+                        proof,
+                        num_iterations: t,
+                    });
+                }
+                for recursion in [2, 1] {
+                    jobs.push(VerifyJob::NWesolowski {
+                        discriminant: disc,
+                        x_s: x,
+                        proof: blob,
+                        num_iterations,
+                        recursion,
+                    });
+                }
+            }
+        }
+
+        let expected: Vec<_> = jobs.iter().map(VerifyJob::verify).collect();
+        assert_eq!(
+            expected
+                .iter()
+                .filter(|result| **result == Ok(true))
+                .count(),
+            60
+        );
+        assert_eq!(expected.iter().filter(|result| result.is_err()).count(), 30);
+        for threads in [0, 1, 4, 200] {
+            assert_eq!(
+                verify_parallel(&jobs, threads),
+                expected,
+                "{} threads",
+                threads
+            );
+        }
+        assert!(verify_parallel(&[], 4).is_empty());
+    }
+
+    #[test]
+    fn test_exponentiation() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let g = generator(&disc).unwrap();
+        let x = power(&disc, &g, b"exponentiation").unwrap();
+
+        // Just past the challenge prime, and far past it
+        let exponents: [&[u8]; 2] = [&[1; B_BYTES + 1], &[0xab; 300]];
+        for exponent in exponents {
+            let (y, proof) = prove_exponentiation(&disc, &x, exponent).unwrap();
+            assert_eq!(y, power(&disc, &x, exponent).unwrap());
+            assert_ne!(proof, identity(&disc).unwrap());
+            assert_eq!(
+                verify_exponentiation(&disc, &x, &y, exponent, &proof),
+                Ok(true)
+            );
+
+            assert_eq!(
+                verify_exponentiation(&disc, &x, &g, exponent, &proof),
+                Ok(false)
+            );
+            assert_eq!(
+                verify_exponentiation(&disc, &x, &y, &[exponent, &[1]].concat(), &proof),
+                Ok(false)
+            );
+        }
+
+        // Exponents up to the size of the challenge prime are recomputed rather than proven
+        let mut exponent_128 = [0; 16];
+        hash_int(b"exponent", &mut exponent_128).unwrap();
+        let short: [&[u8]; 5] = [&[], &[1], &exponent_128, &[0xff; B_BYTES], &[0; 300]];
+        for exponent in short {
+            assert!(matches!(
+                prove_exponentiation(&disc, &x, exponent),
+                Err(ChiavdfError::InvalidInput(_))
+            ));
+            assert!(matches!(
+                verify_exponentiation(&disc, &x, &x, exponent, &x),
+                Err(ChiavdfError::InvalidInput(_))
+            ));
+        }
+
+        // The same exponent with leading zeros has the same proof
+        let (y, proof) = prove_exponentiation(&disc, &x, &[0xab; 300]).unwrap();
+        let padded = [&[0; 4][..], &[0xab; 300]].concat();
+        assert_eq!(
+            verify_exponentiation(&disc, &x, &y, &padded, &proof),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_exponentiation(&disc, &x, &y, &[0xab; 300], &x),
+            Ok(false)
+        );
+        let mut corrupted = proof.clone();
+        corrupted[20] ^= 1;
+        assert!(matches!(
+            verify_exponentiation(&disc, &x, &y, &[0xab; 300], &corrupted),
+            Err(ChiavdfError::InvalidForm(_))
+        ));
+        assert!(verify_exponentiation(&disc, &x, &y, &[0xab; 300], &proof[1..]).is_err());
+        assert!(prove_exponentiation(&disc, &x[1..], &[0xab; 300]).is_err());
+
+        // The challenge depends on the discriminant
+        let other = Discriminant::from_seed(b"Other", 1024).unwrap();
+        let exponent = BigUint::from_bytes_be(&[0xab; 300]);
+        assert_ne!(
+            exponentiation_challenge(&disc, &x, &y, &exponent).unwrap(),
+            exponentiation_challenge(&other, &x, &y, &exponent).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let x = generator(&disc).unwrap();
+        let num_iterations = 100;
+        let (y, proof) = evaluate_and_prove(&disc, &x, num_iterations).unwrap();
+
+        // A wrong proof is reported as invalid, not as an error
+        assert_eq!(verify(&disc, &x, &y, &x, num_iterations), Ok(false));
+
+        // Forms which cannot be decoded are reported as errors
+        let mut corrupted = y.clone();
+        corrupted[20] ^= 1;
+        assert!(matches!(
+            verify(&disc, &x, &corrupted, &proof, num_iterations),
+            Err(ChiavdfError::InvalidForm(_))
+        ));
+        assert_eq!(
+            multiply(&disc, &x, &corrupted),
+            Err(ChiavdfError::InvalidForm(
+                "Deserializing compressed form failed".to_string()
+            ))
+        );
+
+        // Mismatched buffers are rejected before reaching the C++ side
+        assert!(matches!(
+            verify(&disc, &x, &y, &proof[..10], num_iterations),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+
+        // The error of a failed call does not leak into the next successful one
+        assert_eq!(verify(&disc, &x, &y, &proof, num_iterations), Ok(true));
+    }
+
+    #[test]
+    fn test_coefficients() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let d = disc.to_bigint();
+
+        // The identity is (1, 1, (1 - D) / 4) and the generator (2, 1, (1 - D) / 8)
+        let identity = identity(&disc).unwrap();
+        let one = BigInt::from(1);
+        let c = (&one - &d) / 4;
+        assert_eq!(
+            decompress(&disc, &identity).unwrap(),
+            (one.clone(), one.clone(), c)
+        );
+        let generator = generator(&disc).unwrap();
+        let c = (&one - &d) / 8;
+        assert_eq!(
+            decompress(&disc, &generator).unwrap(),
+            (BigInt::from(2), one.clone(), c)
+        );
+
+        // Round trip through the coefficients, covering both signs of b
+        let mut signs = (false, false);
+        for exponent in 1u32..20 {
+            let form = power(&disc, &generator, &exponent.to_be_bytes()).unwrap();
+            let (a, b, c) = decompress(&disc, &form).unwrap();
+            assert_eq!(&b * &b - 4 * &a * &c, d);
+            assert_eq!(from_abc(&disc, &a, &b, &c).unwrap(), form);
+            if b.is_negative() {
+                signs.0 = true;
+            } else {
+                signs.1 = true;
+            }
+        }
+        assert_eq!(signs, (true, true));
+
+        // The inverse of the generator (2, -1, c) is reduced but not a special form
+        let (a, b, c) = decompress(&disc, &generator).unwrap();
+        let inverse = from_abc(&disc, &a, &-&b, &c).unwrap();
+        assert_eq!(multiply(&disc, &generator, &inverse).unwrap(), identity);
+
+        // Coefficients which do not match the discriminant
+        assert!(matches!(
+            from_abc(&disc, &a, &b, &(&c + 1)),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            from_abc(&disc, &-&a, &b, &-&c),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+
+        // (2, 5, c') has the right discriminant but is not reduced
+        let b = BigInt::from(5);
+        let c = (&b * &b - &d) / 8;
+        assert_eq!(
+            from_abc(&disc, &a, &b, &c),
+            Err(ChiavdfError::InvalidForm("Form is not reduced".to_string()))
+        );
+    }
 }
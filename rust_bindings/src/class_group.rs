@@ -0,0 +1,413 @@
+//! Native arithmetic in imaginary quadratic class groups on [BigInt] forms: composition with
+//! NUCOMP, squaring with NUDUPL, reduction and exponentiation, ported from `qfb_nucomp` and
+//! `qfb_nudupl` in src/nucomp.h and from the form reduction in src/vdf_new.h.
+//!
+//! The results are the same as those of the C++ `multiply_wrapper` and `power_wrapper` without
+//! crossing the FFI. Forms passed to the same operation must share the discriminant, which is not
+//! checked.
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+use super::bqfc;
+use super::discriminant::Discriminant;
+use super::error::{ChiavdfError, ChiavdfResult};
+use super::fastcrypto::extended_gcd::extended_euclidean_algorithm;
+use super::xgcd_partial::xgcd_partial;
+
+/// A binary quadratic form (a, b, c) with discriminant b^2 - 4ac.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Qfb {
+    pub a: BigInt,
+    pub b: BigInt,
+    pub c: BigInt,
+}
+
+impl Qfb {
+    /// Build the reduced form equivalent to (a, b, (b^2 - D) / 4a), like `form::from_abd`.
+    pub fn from_abd(a: &BigInt, b: &BigInt, discriminant: &BigInt) -> ChiavdfResult<Self> {
+        if !a.is_positive() {
+            return Err(invalid_form("Invalid form. Positive a"));
+        }
+        let (c, remainder) = (b * b - discriminant).div_mod_floor(&(a << 2));
+        if !remainder.is_zero() {
+            return Err(invalid_form("Invalid form. Can't find c."));
+        }
+        let mut form = Self {
+            a: a.clone(),
+            b: b.clone(),
+            c,
+        };
+        form.reduce();
+        Ok(form)
+    }
+
+    /// Decode a form of the class group of `discriminant` from its compressed encoding, like the
+    /// C++ library does. Fails if the encoding is not canonical or the form is not reduced.
+    pub fn from_bytes(discriminant: &Discriminant, bytes: &[u8]) -> ChiavdfResult<Self> {
+        Self::deserialize(&discriminant.to_bigint(), bytes)
+    }
+
+    /// `DeserializeForm`, failing with the messages of the exceptions thrown by the C++ library.
+    pub(crate) fn deserialize(discriminant: &BigInt, bytes: &[u8]) -> ChiavdfResult<Self> {
+        let (a, b) = bqfc::deserialize(discriminant, bytes, discriminant.bits() as usize)?;
+        let form = Self::from_abd(&a, &b, discriminant)?;
+        if !form.is_reduced() {
+            return Err(invalid_form("Form is not reduced"));
+        }
+        Ok(form)
+    }
+
+    /// The compressed encoding of the form, which must be reduced, in the class group of
+    /// `discriminant`.
+    pub fn to_bytes(&self, discriminant: &Discriminant) -> ChiavdfResult<Vec<u8>> {
+        bqfc::serialize(&self.a, &self.b, discriminant.bits())
+    }
+
+    /// The identity (1, 1, c) of the class group of `discriminant`.
+    pub fn identity(discriminant: &BigInt) -> Self {
+        let one = BigInt::one();
+        Self {
+            c: (&one - discriminant) >> 2,
+            a: one.clone(),
+            b: one,
+        }
+    }
+
+    /// The reduced form equivalent to (2, 1, c), the default VDF input.
+    pub fn generator(discriminant: &BigInt) -> Self {
+        Self::from_abd(&BigInt::from(2), &BigInt::one(), discriminant)
+            .expect("(2, 1, c) is a form of every discriminant = 1 (mod 8)")
+    }
+
+    /// The discriminant b^2 - 4ac.
+    pub fn discriminant(&self) -> BigInt {
+        &self.b * &self.b - ((&self.a * &self.c) << 2)
+    }
+
+    /// The inverse (a, -b, c), reduced.
+    pub fn inverse(&self) -> Self {
+        let mut inverse = Self {
+            a: self.a.clone(),
+            b: -&self.b,
+            c: self.c.clone(),
+        };
+        inverse.reduce();
+        inverse
+    }
+
+    /// Whether `-a < b <= a <= c`, with `b >= 0` if `a == c`.
+    pub fn is_reduced(&self) -> bool {
+        if self.a < self.c || (self.a == self.c && !self.b.is_negative()) {
+            return self.a.magnitude() > self.b.magnitude() || self.a == self.b;
+        }
+        false
+    }
+
+    /// Replace the form by the unique reduced form of its class, like `reduce` in src/vdf_new.h.
+    pub fn reduce(&mut self) {
+        self.normalize();
+        while self.a > self.c || (self.a == self.c && self.b.is_negative()) {
+            let s = (&self.c + &self.b).div_floor(&(&self.c << 1));
+            let b = ((&s * &self.c) << 1) - &self.b;
+            let a = std::mem::replace(&mut self.a, self.c.clone());
+            let b = std::mem::replace(&mut self.b, b);
+            self.c = &self.c * &s * &s - &b * &s + a;
+        }
+        self.normalize();
+    }
+
+    /// Bring b into (-a, a] without changing the class.
+    fn normalize(&mut self) {
+        let r = (&self.a - &self.b).div_floor(&(&self.a << 1));
+        self.c = &self.a * &r * &r + &self.b * &r + &self.c;
+        self.b += (&r * &self.a) << 1;
+    }
+}
+
+/// The NUCOMP bound `L = |D|^(1/4)`.
+pub fn nucomp_bound(discriminant: &BigInt) -> BigInt {
+    BigInt::from(discriminant.magnitude().nth_root(4))
+}
+
+/// Compose `f` and `g`, like `qfb_nucomp`. The result is not reduced.
+pub fn nucomp(f: &Qfb, g: &Qfb, discriminant: &BigInt, bound: &BigInt) -> Qfb {
+    if f.a > g.a {
+        return nucomp(g, f, discriminant, bound);
+    }
+
+    let mut a1 = f.a.clone();
+    let mut a2 = g.a.clone();
+    let mut c2 = g.c.clone();
+
+    let ss: BigInt = (&f.b + &g.b) >> 1;
+    let m: BigInt = (&f.b - &g.b) >> 1;
+
+    let t = a2.mod_floor(&a1);
+    let (sp, v1) = if t.is_zero() {
+        (a1.clone(), BigInt::zero())
+    } else {
+        let output = extended_euclidean_algorithm(&a1, &t, false);
+        (output.gcd, output.y)
+    };
+
+    let mut k = (&m * &v1).mod_floor(&a1);
+
+    if !sp.is_one() {
+        let output = extended_euclidean_algorithm(&ss, &sp, true);
+        let (v2, u2) = (output.x.expect("x is computed"), output.y);
+
+        k = &k * u2 - v2 * &c2;
+
+        if !output.gcd.is_one() {
+            a1 /= &output.gcd;
+            a2 /= &output.gcd;
+            c2 *= &output.gcd;
+        }
+
+        k = k.mod_floor(&a1);
+    }
+
+    if &a1 < bound {
+        let t = &a2 * &k;
+        let cc = ((&g.b + &t) * &k + &c2) / &a1;
+        return Qfb {
+            a: &a2 * &a1,
+            b: (t << 1) + &g.b,
+            c: cc,
+        };
+    }
+
+    let (co2, co1, r1) = xgcd_partial(&a1, &k, bound);
+
+    let t = &a2 * &r1;
+    let m1 = (&m * &co1 + &t) / &a1;
+    let m2 = (&ss * &r1 - &c2 * &co1) / &a1;
+
+    let mut ca = if co1.is_negative() {
+        &r1 * &m1 - &co1 * &m2
+    } else {
+        &co1 * &m2 - &r1 * &m1
+    };
+
+    let cb: BigInt = ((t - &ca * &co2) << 1) / &co1 - &g.b;
+    let cb = cb.mod_floor(&(&ca << 1));
+    let mut cc: BigInt = ((&cb * &cb - discriminant) / &ca) >> 2;
+
+    if ca.is_negative() {
+        ca = -ca;
+        cc = -cc;
+    }
+    Qfb {
+        a: ca,
+        b: cb,
+        c: cc,
+    }
+}
+
+/// Square `f`, like `qfb_nudupl`. The result is not reduced.
+pub fn nudupl(f: &Qfb, discriminant: &BigInt, bound: &BigInt) -> Qfb {
+    let output = extended_euclidean_algorithm(&f.a, &f.b, false);
+    let mut a1 = f.a.clone();
+    let mut c1 = f.c.clone();
+
+    // k = -(c * inv(b)) (mod a)
+    let mut k = -(output.y * &c1);
+
+    if !output.gcd.is_one() {
+        a1 = output.a_divided_by_gcd.abs();
+        c1 *= &output.gcd;
+    }
+
+    k = k.mod_floor(&a1);
+
+    if &a1 < bound {
+        let t = &a1 * &k;
+        let c = ((&f.b + &t) * &k + &c1).div_floor(&a1);
+        return Qfb {
+            a: &a1 * &a1,
+            b: (t << 1) + &f.b,
+            c,
+        };
+    }
+
+    let (co2, co1, r1) = xgcd_partial(&a1, &k, bound);
+
+    let m2 = (&f.b * &r1 - &c1 * &co1) / &a1;
+
+    let mut a = &r1 * &r1 - &co1 * &m2;
+    if !co1.is_negative() {
+        a = -a;
+    }
+
+    let b: BigInt = ((&a1 * &r1 - &a * &co2) << 1) / &co1 - &f.b;
+    let b = b.mod_floor(&(&a << 1));
+    let mut c: BigInt = (&b * &b - discriminant) / &a / 4;
+
+    if a.is_negative() {
+        a = -a;
+        c = -c;
+    }
+    Qfb { a, b, c }
+}
+
+/// Square and reduce `f`.
+pub fn square(f: &Qfb, discriminant: &BigInt, bound: &BigInt) -> Qfb {
+    let mut result = nudupl(f, discriminant, bound);
+    result.reduce();
+    result
+}
+
+/// Compose and reduce `f` and `g`, the group operation.
+pub fn multiply(f: &Qfb, g: &Qfb, discriminant: &BigInt, bound: &BigInt) -> Qfb {
+    let mut result = nucomp(f, g, discriminant, bound);
+    result.reduce();
+    result
+}
+
+/// Raise `x` to the power `exponent` by square and multiply from the top bit, like
+/// `FastPowFormNucomp`. The result is reduced.
+pub fn pow(x: &Qfb, exponent: &BigUint, discriminant: &BigInt, bound: &BigInt) -> Qfb {
+    if exponent.is_zero() {
+        return Qfb::identity(discriminant);
+    }
+
+    // Reduce only when `a` exceeds half of the discriminant size
+    let max_bits = discriminant.bits() / 2;
+    let mut result = x.clone();
+    for i in (0..exponent.bits() - 1).rev() {
+        result = nudupl(&result, discriminant, bound);
+        if result.a.bits() > max_bits {
+            result.reduce();
+        }
+        if exponent.bit(i) {
+            result = nucomp(&result, x, discriminant, bound);
+        }
+    }
+    result.reduce();
+    result
+}
+
+fn invalid_form(message: &str) -> ChiavdfError {
+    ChiavdfError::InvalidForm(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// -(2^127 - 1), a discriminant = 1 (mod 8) which does not need the C++ library to build
+    fn discriminant() -> Discriminant {
+        let magnitude: BigUint = (BigUint::one() << 127u32) - 1u32;
+        Discriminant::from_bytes(&magnitude.to_bytes_be()).unwrap()
+    }
+
+    #[test]
+    fn test_group_laws() {
+        let discriminant = discriminant();
+        let d = discriminant.to_bigint();
+        let bound = nucomp_bound(&d);
+        let identity = Qfb::identity(&d);
+        let generator = Qfb::generator(&d);
+
+        assert_eq!(multiply(&identity, &generator, &d, &bound), generator);
+        assert_eq!(
+            multiply(&generator, &generator.inverse(), &d, &bound),
+            identity
+        );
+        assert_eq!(
+            square(&generator, &d, &bound),
+            multiply(&generator, &generator, &d, &bound)
+        );
+
+        let mut expected = identity.clone();
+        for exponent in 0u32..64 {
+            let power = pow(&generator, &BigUint::from(exponent), &d, &bound);
+            assert_eq!(power, expected);
+            assert!(power.is_reduced());
+            assert_eq!(power.discriminant(), d);
+            let bytes = power.to_bytes(&discriminant).unwrap();
+            assert_eq!(Qfb::from_bytes(&discriminant, &bytes).unwrap(), power);
+            expected = multiply(&expected, &generator, &d, &bound);
+        }
+
+        let (e1, e2) = (BigUint::from(987_654_321u64), BigUint::from(123_456_789u64));
+        let x = pow(&generator, &e1, &d, &bound);
+        let y = pow(&generator, &e2, &d, &bound);
+        assert_eq!(
+            multiply(&x, &y, &d, &bound),
+            pow(&generator, &(&e1 + &e2), &d, &bound)
+        );
+        assert_eq!(
+            pow(&x, &e2, &d, &bound),
+            pow(&generator, &(&e1 * &e2), &d, &bound)
+        );
+    }
+
+    #[test]
+    fn test_from_abd() {
+        let d = discriminant().to_bigint();
+        let one = BigInt::one();
+        assert_eq!(Qfb::from_abd(&one, &one, &d).unwrap(), Qfb::identity(&d));
+        assert_eq!(
+            Qfb::from_abd(&BigInt::zero(), &one, &d),
+            Err(invalid_form("Invalid form. Positive a"))
+        );
+        assert_eq!(
+            Qfb::from_abd(&BigInt::from(3), &one, &d),
+            Err(invalid_form("Invalid form. Can't find c."))
+        );
+
+        // An unreduced form is reduced to the same class
+        let generator = Qfb::generator(&d);
+        let unreduced = Qfb::from_abd(&BigInt::from(2), &BigInt::from(5), &d).unwrap();
+        assert_eq!(unreduced, generator);
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_matches_c_multiply_and_power() {
+        use crate::c_bindings;
+
+        for d_bits in [512, 1024, 2048] {
+            let discriminant = Discriminant::from_seed(b"class group", d_bits).unwrap();
+            let d = discriminant.to_bigint();
+            let bound = nucomp_bound(&d);
+            let generator_s = c_bindings::generator(&discriminant).unwrap();
+
+            let forms: Vec<Vec<u8>> = (1u64..8)
+                .map(|i| {
+                    let exponent = i.wrapping_mul(0x9e37_79b9_7f4a_7c15).to_be_bytes();
+                    c_bindings::power(&discriminant, &generator_s, &exponent).unwrap()
+                })
+                .collect();
+
+            for (x_s, y_s) in forms.iter().zip(forms.iter().rev()) {
+                let x = Qfb::from_bytes(&discriminant, x_s).unwrap();
+                let y = Qfb::from_bytes(&discriminant, y_s).unwrap();
+
+                let product = multiply(&x, &y, &d, &bound);
+                let expected = c_bindings::multiply(&discriminant, x_s, y_s).unwrap();
+                assert_eq!(product.to_bytes(&discriminant).unwrap(), expected);
+
+                let squared = square(&x, &d, &bound);
+                let expected = c_bindings::multiply(&discriminant, x_s, x_s).unwrap();
+                assert_eq!(squared.to_bytes(&discriminant).unwrap(), expected);
+
+                for exponent in [
+                    BigUint::from(0u32),
+                    BigUint::from(1u32),
+                    BigUint::from(255u32),
+                    BigUint::from(u64::MAX),
+                    BigUint::from_bytes_be(&y_s[..40]),
+                ] {
+                    let power = pow(&x, &exponent, &d, &bound);
+                    let expected =
+                        c_bindings::power(&discriminant, x_s, &exponent.to_bytes_be()).unwrap();
+                    assert_eq!(power.to_bytes(&discriminant).unwrap(), expected);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,20 @@
 // Discriminant size for test and benches
 pub const DISCRIMINANT_SIZE: usize = 4096;
 
-// hard coded class group's element size in bqfc.h
-// if BQFC_MAX_D_BITS is changed, this value must be changed accordingly
-pub const FORM_SIZE: usize = 388;
+// largest discriminant size supported by the form encoding, BQFC_MAX_D_BITS in bqfc.h
+pub const MAX_DISCRIMINANT_SIZE: usize = 4096;
+
+// size of a serialized class group element for a discriminant of `d_bits` bits,
+// bqfc_get_compr_size in bqfc.c
+pub const fn form_size_for_bits(d_bits: usize) -> usize {
+    d_bits.div_ceil(32) * 3 + 4
+}
+
+// size of the class group elements of the largest discriminants, BQFC_FORM_SIZE in bqfc.h
+pub const FORM_SIZE: usize = form_size_for_bits(MAX_DISCRIMINANT_SIZE);
+
+// bit length of the Fiat-Shamir challenge prime B, B_bits in proof_common.h
+pub const B_BITS: usize = 264;
+
+// size of B in the N-Wesolowski proof segments, B_bytes in proof_common.h
+pub const B_BYTES: usize = B_BITS.div_ceil(8);
@@ -0,0 +1,124 @@
+//! Rust implementations of `CreateDiscriminant`, `HashInt` and `HashPrime`, which don't need the
+//! C++ library. They write the same bytes as the wrappers in [crate::c_bindings] with the same
+//! arguments, using a port of the Baillie-PSW test behind GMP's `prime()`.
+
+use num_bigint::BigUint;
+
+use super::error::{ChiavdfError, ChiavdfResult};
+use super::proof_common;
+
+/// Derive the absolute value of a discriminant of `result.len() * 8` bits from a seed. The result
+/// is a prime |D| with -D = 1 (mod 8) and the top bit set.
+pub fn create_discriminant(seed: &[u8], result: &mut [u8]) -> ChiavdfResult<()> {
+    if result.is_empty() {
+        return Err(ChiavdfError::InvalidInput(
+            "The discriminant size must not be zero".to_string(),
+        ));
+    }
+    let discriminant = proof_common::create_discriminant(seed, result.len() * 8);
+    export(&discriminant, result);
+    Ok(())
+}
+
+/// Hash a seed to an integer of `result.len() * 8` bits. At most 32 bytes are written.
+pub fn hash_int(seed: &[u8], result: &mut [u8]) -> ChiavdfResult<()> {
+    export(&proof_common::hash_int(seed, result.len() * 8), result);
+    Ok(())
+}
+
+/// Hash a seed to a prime of `result.len() * 8` bits with the top bit set.
+pub fn hash_prime(seed: &[u8], result: &mut [u8]) -> ChiavdfResult<()> {
+    if result.is_empty() {
+        return Err(ChiavdfError::InvalidInput(
+            "The prime size must not be zero".to_string(),
+        ));
+    }
+    let length = result.len() * 8;
+    let prime = proof_common::hash_prime(seed, length, &[length as u64 - 1]);
+    export(&prime, result);
+    Ok(())
+}
+
+// Write the big-endian bytes of `value` without leading zeros at the start of `result` and leave
+// the rest untouched, like `mpz_export` in the C++ wrappers.
+fn export(value: &BigUint, result: &mut [u8]) {
+    if value.bits() == 0 {
+        return;
+    }
+    let bytes = value.to_bytes_be();
+    result[..bytes.len()].copy_from_slice(&bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    #[test]
+    fn test_create_discriminant() {
+        let seeds = [
+            hex!("6c3b9aa767f785b537c0"),
+            hex!("b10da48cea4c09676b8e"),
+            hex!("c51b8a31c98b9fe13065"),
+            hex!("5de9bc1bb4cb7a9f9cf9"),
+            hex!("22cfaefc92e4edb9b0ae"),
+        ];
+
+        // The same vectors as the C++ bindings test, from the Python `create_discriminant`
+        let expected = [
+            "9a8eaf9c52d9a5f1db648cdf7bcd04b35cb1ac4f421c978fa61fe1344b97d4199dbff700d24e7cfc0b785e4b8b8023dc49f0e90227f74f54234032ac3381879f",
+            "b193cdb02f1c2615a257b98933ee0d24157ac5f8c46774d5d635022e6e6bd3f7372898066c2a40fa211d1df8c45cb95c02e36ef878bc67325473d9c0bb34b047",
+            "bb5bd19ae50efe98b5ac56c69453a95e92dc16bb4b2824e73b39b9db0a077fa33fc2e775958af14f675a071bf53f1c22f90ccbd456e2291276951830dba9dcaf",
+            "a1e93b8f2e9b0fd3b1325fbe40601f55e2afbdc6161409c0aff8737b7213d7d71cab21ffc83a0b6d5bdeee2fdcbbb34fbc8fc0b439915075afa9ffac8bb1b337",
+            "f2a10f70148fb30e4a16c4eda44cc0f9917cb9c2d460926d59a408318472e2cfd597193aa58e1fdccc6ae6a4d85bc9b27f77567ebe94fcedbf530a60ff709fd7",
+        ];
+
+        for (seed, expected) in seeds.iter().zip(expected) {
+            let mut discriminant = [0; 64];
+            create_discriminant(seed, &mut discriminant).unwrap();
+            assert_eq!(
+                hex::encode(discriminant),
+                expected,
+                "seed is {}",
+                hex::encode(seed)
+            );
+        }
+
+        assert!(create_discriminant(b"seed", &mut []).is_err());
+        assert!(hash_prime(b"seed", &mut []).is_err());
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_matches_c() {
+        use crate::c_bindings;
+
+        for i in 0u32..300 {
+            let seed = i.to_be_bytes();
+
+            // Some of the 16-byte hashes have a leading zero byte
+            for size in [1, 16, 32, 40] {
+                let (mut expected, mut actual) = (vec![0xaa; size], vec![0xaa; size]);
+                c_bindings::hash_int(&seed, &mut expected).unwrap();
+                hash_int(&seed, &mut actual).unwrap();
+                assert_eq!(actual, expected, "hash_int {} {}", i, size);
+            }
+
+            for size in [1, 8, 33] {
+                let (mut expected, mut actual) = (vec![0; size], vec![0; size]);
+                c_bindings::hash_prime(&seed, &mut expected).unwrap();
+                hash_prime(&seed, &mut actual).unwrap();
+                assert_eq!(actual, expected, "hash_prime {} {}", i, size);
+            }
+        }
+
+        for (i, size) in [1, 16, 64, 128, 256].into_iter().enumerate() {
+            let seed = [i as u8; 10];
+            let (mut expected, mut actual) = (vec![0; size], vec![0; size]);
+            c_bindings::create_discriminant(&seed, &mut expected).unwrap();
+            create_discriminant(&seed, &mut actual).unwrap();
+            assert_eq!(actual, expected, "create_discriminant {}", size);
+        }
+    }
+}
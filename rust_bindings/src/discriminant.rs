@@ -0,0 +1,152 @@
+use std::fmt;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_prime::nt_funcs::is_prime;
+use num_traits::{Signed, Zero};
+
+#[cfg(feature = "cpp")]
+use super::c_bindings::create_discriminant;
+use super::constants::{form_size_for_bits, MAX_DISCRIMINANT_SIZE};
+#[cfg(not(feature = "cpp"))]
+use super::create_discriminant::create_discriminant;
+
+/// The discriminant D of an imaginary quadratic class group.
+///
+/// A `Discriminant` is always negative, prime, congruent to 1 modulo 4 and at most
+/// [MAX_DISCRIMINANT_SIZE] bits long. These invariants are checked once when the value is
+/// built, so the prove, verify and group functions can rely on them.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Discriminant {
+    // Big-endian magnitude |D| without leading zeros, as expected by the C++ wrappers.
+    bytes: Vec<u8>,
+}
+
+impl Discriminant {
+    /// Derive a discriminant of `size_bits` bits from a seed, like `CreateDiscriminant`.
+    /// `size_bits` must be a non-zero multiple of 8 no larger than [MAX_DISCRIMINANT_SIZE].
+    ///
+    /// The C++ implementation is used with the `cpp` feature, as GMP is much faster at the
+    /// primality tests for large sizes. Both give the same discriminant.
+    pub fn from_seed(seed: &[u8], size_bits: usize) -> Option<Self> {
+        if size_bits == 0 || !size_bits.is_multiple_of(8) || size_bits > MAX_DISCRIMINANT_SIZE {
+            return None;
+        }
+        let mut bytes = vec![0; size_bits / 8];
+        create_discriminant(seed, &mut bytes).ok()?;
+        // `CreateDiscriminant` sets the top bit and only returns primes -D with D = 1 (mod 4).
+        Some(Self { bytes })
+    }
+
+    /// Build a discriminant from the big-endian bytes of its absolute value |D|. The sign is
+    /// always taken to be negative.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::from_magnitude(BigUint::from_bytes_be(bytes))
+    }
+
+    /// Parse a discriminant from its decimal representation, e.g. the string returned by the
+    /// Python `create_discriminant`. The value must carry its negative sign.
+    pub fn from_decimal(s: &str) -> Option<Self> {
+        let value = BigInt::parse_bytes(s.as_bytes(), 10)?;
+        if !value.is_negative() {
+            return None;
+        }
+        Self::from_magnitude(value.into_parts().1)
+    }
+
+    fn from_magnitude(magnitude: BigUint) -> Option<Self> {
+        if magnitude.is_zero() || magnitude.bits() > MAX_DISCRIMINANT_SIZE as u64 {
+            return None;
+        }
+        // D = 1 (mod 4) if and only if |D| = 3 (mod 4).
+        if (&magnitude % 4u32) != BigUint::from(3u32) {
+            return None;
+        }
+        if !is_prime(&magnitude, None).probably() {
+            return None;
+        }
+        Some(Self {
+            bytes: magnitude.to_bytes_be(),
+        })
+    }
+
+    /// The big-endian bytes of |D|, the layout the C++ wrappers import the discriminant from.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// The bit length of |D|.
+    pub fn bits(&self) -> usize {
+        self.to_bigint().bits() as usize
+    }
+
+    /// The size of the serialized forms of this discriminant.
+    pub fn form_size(&self) -> usize {
+        form_size_for_bits(self.bits())
+    }
+
+    /// The (negative) value of the discriminant.
+    pub fn to_bigint(&self) -> BigInt {
+        BigInt::from_bytes_be(Sign::Minus, &self.bytes)
+    }
+}
+
+impl fmt::Display for Discriminant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_bigint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_seed() {
+        let discriminant = Discriminant::from_seed(b"HelloWorld", 512).unwrap();
+        assert_eq!(discriminant.bits(), 512);
+        assert_eq!(discriminant.as_bytes().len(), 64);
+
+        // Sizes must be whole bytes and fit the form encoding
+        assert!(Discriminant::from_seed(b"HelloWorld", 0).is_none());
+        assert!(Discriminant::from_seed(b"HelloWorld", 500).is_none());
+        assert!(Discriminant::from_seed(b"HelloWorld", MAX_DISCRIMINANT_SIZE + 8).is_none());
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let discriminant = Discriminant::from_seed(b"HelloWorld", 512).unwrap();
+
+        let from_bytes = Discriminant::from_bytes(discriminant.as_bytes()).unwrap();
+        assert_eq!(from_bytes, discriminant);
+
+        let decimal = discriminant.to_string();
+        assert!(decimal.starts_with('-'));
+        let from_decimal = Discriminant::from_decimal(&decimal).unwrap();
+        assert_eq!(from_decimal, discriminant);
+
+        // Leading zeros are not part of the value
+        let mut padded = vec![0, 0];
+        padded.extend_from_slice(discriminant.as_bytes());
+        assert_eq!(Discriminant::from_bytes(&padded).unwrap(), discriminant);
+    }
+
+    #[test]
+    fn test_invalid_discriminants() {
+        // 23 is prime and -23 = 1 (mod 4)
+        assert!(Discriminant::from_decimal("-23").is_some());
+        // The sign is mandatory in the decimal representation
+        assert!(Discriminant::from_decimal("23").is_none());
+        // -21 = 3 (mod 4)
+        assert!(Discriminant::from_decimal("-21").is_none());
+        // -35 = 1 (mod 4) but is not prime
+        assert!(Discriminant::from_decimal("-35").is_none());
+        assert!(Discriminant::from_decimal("-0").is_none());
+        assert!(Discriminant::from_decimal("not a number").is_none());
+        assert!(Discriminant::from_bytes(&[]).is_none());
+
+        // Too large for the form encoding
+        let mut too_large = vec![0xff; MAX_DISCRIMINANT_SIZE / 8 + 1];
+        too_large[0] = 0x01;
+        assert!(Discriminant::from_bytes(&too_large).is_none());
+    }
+}
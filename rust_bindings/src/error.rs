@@ -0,0 +1,54 @@
+use std::fmt;
+
+/// Messages of the exceptions thrown by the C++ library when a form cannot be decoded.
+#[cfg(feature = "cpp")]
+const INVALID_FORM_MESSAGES: [&str; 4] = [
+    "Deserializing compressed form failed",
+    "Form is not reduced",
+    "Invalid form. Positive a",
+    "Invalid form. Can't find c.",
+];
+
+/// The reasons a call into chiavdf can fail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChiavdfError {
+    /// A form could not be decoded: the encoding is malformed, does not belong to the class group
+    /// of the discriminant or the form is not reduced. Carries the C++ exception message.
+    InvalidForm(String),
+    /// An argument was rejected before reaching the C++ library, e.g. a buffer of the wrong size.
+    InvalidInput(String),
+    /// The C++ library ran out of memory.
+    OutOfMemory,
+    /// Any other exception thrown by the C++ library, with its message.
+    Exception(String),
+    /// The computation was stopped through its cancellation flag.
+    Cancelled,
+}
+
+pub type ChiavdfResult<T> = Result<T, ChiavdfError>;
+
+#[cfg(feature = "cpp")]
+impl ChiavdfError {
+    /// Classify a `std::exception` by its message.
+    pub(crate) fn from_exception_message(message: String) -> Self {
+        if INVALID_FORM_MESSAGES.contains(&message.as_str()) {
+            ChiavdfError::InvalidForm(message)
+        } else {
+            ChiavdfError::Exception(message)
+        }
+    }
+}
+
+impl fmt::Display for ChiavdfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChiavdfError::InvalidForm(message) => write!(f, "invalid form: {}", message),
+            ChiavdfError::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            ChiavdfError::OutOfMemory => write!(f, "out of memory"),
+            ChiavdfError::Exception(message) => write!(f, "chiavdf exception: {}", message),
+            ChiavdfError::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for ChiavdfError {}
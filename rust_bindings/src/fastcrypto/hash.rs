@@ -1,14 +1,16 @@
 // Adapted from https://github.com/MystenLabs/fastcrypto/commit/0acf0ff1a163c60e0dec1e16e4fbad4a4cf853bd
 
 use crate::c_bindings;
+use crate::create_discriminant::hash_prime;
+use crate::discriminant::Discriminant;
 
-use lazy_static::lazy_static;
 use num_bigint::{BigInt, Sign};
 use num_integer::Integer;
 use num_traits::{FromPrimitive, One, Signed};
 use std::ops::{Shl, Shr};
 
 use super::crt::solve_congruence_equation_system;
+use super::jacobi::jacobi;
 use super::modular_sqrt::modular_square_root;
 use sha2::{Digest, Sha256};
 
@@ -37,8 +39,8 @@ const DEFAULT_PRIME_FACTOR_SIZE_IN_BYTES: u64 = 20;
 ///
 /// This method returns an [InvalidInput] error if the discriminant is so small that there are
 /// no secure parameters, and it may also happen if the discriminant is not a prime.
-pub fn hash_to_class_group(seed: &[u8], discriminant: &[u8]) -> Option<Vec<u8>> {
-    if BigInt::from_bytes_be(Sign::Minus, discriminant).bits() <= MINIMAL_DISCRIMINANT_SIZE {
+pub fn hash_to_class_group(seed: &[u8], discriminant: &Discriminant) -> Option<Vec<u8>> {
+    if discriminant.bits() as u64 <= MINIMAL_DISCRIMINANT_SIZE {
         return None;
     }
     hash_to_group_with_custom_parameters(seed, discriminant, DEFAULT_PRIME_FACTORS)
@@ -55,7 +57,7 @@ pub fn hash_to_class_group(seed: &[u8], discriminant: &[u8]) -> Option<Vec<u8>>
 /// use cases, [hash_to_group] should be used.
 fn hash_to_group_with_custom_parameters(
     seed: &[u8],
-    discriminant: &[u8],
+    discriminant: &Discriminant,
     prime_factors: u64,
 ) -> Option<Vec<u8>> {
     //////// OLD updated checks
@@ -71,7 +73,7 @@ fn hash_to_group_with_custom_parameters(
     );
 
     // Ensure that the result will be reduced
-    let discriminant_bigint: BigInt = BigInt::from_bytes_be(Sign::Minus, discriminant);
+    let discriminant_bigint: BigInt = discriminant.to_bigint();
     let sqrt_disc_over_2 = discriminant_bigint.abs().sqrt().shr(1);
     debug_assert!(
         sqrt_disc_over_2 > BigInt::one().shl(prime_factors * DEFAULT_PRIME_FACTOR_SIZE_IN_BYTES)
@@ -82,12 +84,12 @@ fn hash_to_group_with_custom_parameters(
     debug_assert!(prime_factors >= 1);
 
     // Discriminant = 1 (mod 4)
-    debug_assert!(discriminant_bigint.clone() % BigInt::from_u8(4)? == BigInt::one());
+    debug_assert!(discriminant_bigint.mod_floor(&BigInt::from_u8(4)?) == BigInt::one());
 
     // We assume the discriminant is prime
 
     // p~(2^{2 SECURITY_PARAMETER_IN_BITS}) < sqrt(- Discriminant) / 2
-    let (_lower, upper) = p_tilde_primes(1u64.shl(2 * SECURITY_PARAMETER_IN_BITS));
+    let (_lower, upper) = p_tilde_primes(2f64.powi(2 * SECURITY_PARAMETER_IN_BITS as i32));
     debug_assert!(sqrt_disc_over_2 > BigInt::from_f64(upper.ceil()).unwrap());
 
     // Sample a and b such that a < sqrt(|discriminant|)/2 has exactly prime_factors prime factors and b is the square root of the discriminant modulo a.
@@ -98,7 +100,7 @@ fn hash_to_group_with_custom_parameters(
         b -= &a;
     }
 
-    c_bindings::from_ab(discriminant, &a.to_bytes_be().1, &b.to_bytes_be().1)
+    c_bindings::from_ab(discriminant, &a.to_bytes_be().1, &b.to_bytes_be().1).ok()
 }
 
 /// Sample a product of `prime_factors` primes each of size `prime_factor_size_in_bytes` and return
@@ -106,7 +108,7 @@ fn hash_to_group_with_custom_parameters(
 /// prime, an [InvalidInput] error may be returned.
 fn sample_modulus(
     seed: &[u8],
-    discriminant: &[u8],
+    discriminant: &Discriminant,
     prime_factors: u64,
 ) -> Option<(BigInt, BigInt)> {
     // Seed a rng with the hash of the seed
@@ -114,19 +116,24 @@ fn sample_modulus(
     let mut factors: Vec<BigInt> = Vec::with_capacity(prime_factors as usize);
     let mut square_roots: Vec<BigInt> = Vec::with_capacity(prime_factors as usize);
 
-    let discriminant_bigint: BigInt = BigInt::from_bytes_be(Sign::Minus, discriminant);
+    let discriminant_bigint: BigInt = discriminant.to_bigint();
 
     // Create a first factor of size lambda bits
     let mut big_factor_u8 = [0u8; SECURITY_PARAMETER_IN_BITS as usize / 8];
+    let mut big_factor: BigInt;
     loop {
-        if !c_bindings::hash_prime(&rng, &mut big_factor_u8) {
+        if hash_prime(&rng, &mut big_factor_u8).is_err() {
             continue;
         }
         rng = Sha256::digest(rng);
+        big_factor = BigInt::from_bytes_be(Sign::Plus, &big_factor_u8);
+
+        // The discriminant must be a square modulo the factor for the form to exist
+        if jacobi(&discriminant_bigint, &big_factor).unwrap() != 1 {
+            continue;
+        }
         break;
     }
-    // This only fails if the discriminant is not prime.
-    let big_factor = BigInt::from_bytes_be(Sign::Plus, &big_factor_u8);
     let big_square_root = modular_square_root(&discriminant_bigint, &big_factor, false).unwrap();
     factors.push(big_factor);
     square_roots.push(big_square_root);
@@ -136,18 +143,17 @@ fn sample_modulus(
         let mut factor_u8 = [0u8; DEFAULT_PRIME_FACTOR_SIZE_IN_BYTES as usize];
         let mut factor: BigInt;
         loop {
-            if !c_bindings::hash_prime(&rng, &mut factor_u8) {
+            if hash_prime(&rng, &mut factor_u8).is_err() {
                 continue;
             }
             rng = Sha256::digest(rng);
             factor = BigInt::from_bytes_be(Sign::Plus, &factor_u8);
 
-            if factors.contains(&factor) {
+            if factors.contains(&factor) || jacobi(&discriminant_bigint, &factor).unwrap() != 1 {
                 continue;
             }
             break;
         }
-        // This only fails if the discriminant is not prime.
         let square_root = modular_square_root(&discriminant_bigint, &factor, false).unwrap();
         factors.push(factor);
         square_roots.push(square_root);
@@ -173,16 +179,12 @@ fn n_bit_primes(n: u64) -> f64 {
 }
 
 /// Returns a lower and upper bound on the number of prime smaller than n
-fn p_tilde_primes(n: u64) -> (f64, f64) {
+fn p_tilde_primes(n: f64) -> (f64, f64) {
     // Compute the nth prime given the prime-counting theorem
     //
     // "An Efficient Hash Function" uses floor( n ln(n) ) < p~(n)
     // However, in Robin 83 (Estimation de la fonction de Tchebychef...)
     // gives a better approximation n(ln n + ln(ln(n)) - 1) < p~(n) for n > 2 and  p~(n)< n ln(n) + n ln(ln(n)) for n > 6
-    assert!(n > 6);
-    let n_f64 = n as f64;
-    (
-        n_f64 * (n_f64.ln() + n_f64.ln().ln() - 1.0),
-        n_f64 * (n_f64.ln() + n_f64.ln().ln()),
-    )
+    assert!(n > 6.0);
+    (n * (n.ln() + n.ln().ln() - 1.0), n * (n.ln() + n.ln().ln()))
 }
@@ -1,8 +1,11 @@
 // Copyright (c) 2022, Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "cpp")]
 pub(crate) mod crt;
 pub(crate) mod extended_gcd;
+#[cfg(feature = "cpp")]
 pub mod hash;
 pub(crate) mod jacobi;
+#[cfg(feature = "cpp")]
 pub(crate) mod modular_sqrt;
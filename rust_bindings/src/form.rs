@@ -0,0 +1,207 @@
+use std::ops::Mul;
+
+use num_bigint::{BigInt, BigUint};
+
+use super::c_bindings;
+use super::discriminant::Discriminant;
+use super::error::{ChiavdfError, ChiavdfResult};
+
+/// A reduced binary quadratic form (a, b, c), i.e. an element of the class group of its
+/// discriminant.
+///
+/// The form is kept in the compressed encoding used by the C++ library, which is canonical for
+/// reduced forms, so two forms are equal if and only if their encodings are.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QuadraticForm {
+    discriminant: Discriminant,
+    bytes: Vec<u8>,
+}
+
+impl QuadraticForm {
+    /// Decode a form of the class group of `discriminant`. Fails if the bytes are not the
+    /// canonical encoding of a reduced form with this discriminant.
+    pub fn from_bytes(discriminant: &Discriminant, bytes: &[u8]) -> ChiavdfResult<Self> {
+        // Composing with the identity decodes, checks and re-encodes the form.
+        let identity = c_bindings::identity(discriminant)?;
+        let canonical = c_bindings::multiply(discriminant, bytes, &identity)?;
+        if canonical != bytes {
+            return Err(ChiavdfError::InvalidForm(
+                "Form encoding is not canonical".to_string(),
+            ));
+        }
+        Ok(Self::from_trusted_bytes(discriminant, canonical))
+    }
+
+    /// Build the reduced form (a, b, c) of the class group of `discriminant`.
+    pub fn from_abc(
+        discriminant: &Discriminant,
+        a: &BigInt,
+        b: &BigInt,
+        c: &BigInt,
+    ) -> ChiavdfResult<Self> {
+        let bytes = c_bindings::from_abc(discriminant, a, b, c)?;
+        Ok(Self::from_trusted_bytes(discriminant, bytes))
+    }
+
+    /// Wrap bytes returned by the C++ library for `discriminant` without decoding them again.
+    pub(crate) fn from_trusted_bytes(discriminant: &Discriminant, bytes: Vec<u8>) -> Self {
+        Self {
+            discriminant: discriminant.clone(),
+            bytes,
+        }
+    }
+
+    /// The identity (1, 1, c) of the class group.
+    pub fn identity(discriminant: &Discriminant) -> Self {
+        let bytes = c_bindings::identity(discriminant).expect("The discriminant is valid");
+        Self::from_trusted_bytes(discriminant, bytes)
+    }
+
+    /// The generator (2, 1, c) used as the default VDF input.
+    pub fn generator(discriminant: &Discriminant) -> Self {
+        let bytes = c_bindings::generator(discriminant).expect("The discriminant is valid");
+        Self::from_trusted_bytes(discriminant, bytes)
+    }
+
+    /// The discriminant of the class group this form belongs to.
+    pub fn discriminant(&self) -> &Discriminant {
+        &self.discriminant
+    }
+
+    /// The compressed encoding of the form, as taken by the prove and verify functions.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consume the form and return its compressed encoding.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// The coefficients (a, b, c) of the form.
+    pub fn coefficients(&self) -> (BigInt, BigInt, BigInt) {
+        c_bindings::decompress(&self.discriminant, &self.bytes).expect("The form is valid")
+    }
+
+    /// Raise the form to the power `exponent`.
+    pub fn pow(&self, exponent: &BigUint) -> Self {
+        let bytes = c_bindings::power(&self.discriminant, &self.bytes, &exponent.to_bytes_be())
+            .expect("The form is valid");
+        Self::from_trusted_bytes(&self.discriminant, bytes)
+    }
+}
+
+impl Mul<&QuadraticForm> for &QuadraticForm {
+    type Output = QuadraticForm;
+
+    /// Compose two forms. Panics if they do not share the same discriminant.
+    fn mul(self, rhs: &QuadraticForm) -> QuadraticForm {
+        assert_eq!(
+            self.discriminant, rhs.discriminant,
+            "Forms must have the same discriminant"
+        );
+        let bytes = c_bindings::multiply(&self.discriminant, &self.bytes, &rhs.bytes)
+            .expect("The forms are valid");
+        QuadraticForm::from_trusted_bytes(&self.discriminant, bytes)
+    }
+}
+
+impl Mul<&QuadraticForm> for QuadraticForm {
+    type Output = QuadraticForm;
+
+    fn mul(self, rhs: &QuadraticForm) -> QuadraticForm {
+        &self * rhs
+    }
+}
+
+impl Mul for QuadraticForm {
+    type Output = QuadraticForm;
+
+    fn mul(self, rhs: QuadraticForm) -> QuadraticForm {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{One, Zero};
+
+    use super::*;
+
+    #[test]
+    fn test_group_operations() {
+        let discriminant = Discriminant::from_seed(b"HelloWorld", 512).unwrap();
+        let identity = QuadraticForm::identity(&discriminant);
+        let generator = QuadraticForm::generator(&discriminant);
+
+        assert_ne!(identity, generator);
+        assert_eq!(&generator * &identity, generator);
+        assert_eq!(generator.pow(&BigUint::zero()), identity);
+        assert_eq!(generator.pow(&BigUint::one()), generator);
+
+        // g^5 * g^7 = g^12 = (g^3)^4
+        let g5 = generator.pow(&BigUint::from(5u32));
+        let g7 = generator.pow(&BigUint::from(7u32));
+        let g12 = generator.pow(&BigUint::from(12u32));
+        assert_eq!(&g5 * &g7, g12);
+        assert_eq!(&g7 * &g5, g12);
+        assert_eq!(
+            generator
+                .pow(&BigUint::from(3u32))
+                .pow(&BigUint::from(4u32)),
+            g12
+        );
+
+        // Repeated composition matches exponentiation
+        let mut product = identity.clone();
+        for _ in 0..12 {
+            product = product * &generator;
+        }
+        assert_eq!(product, g12);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let discriminant = Discriminant::from_seed(b"HelloWorld", 512).unwrap();
+        let form = QuadraticForm::generator(&discriminant).pow(&BigUint::from(1234u32));
+
+        let decoded = QuadraticForm::from_bytes(&discriminant, form.as_bytes()).unwrap();
+        assert_eq!(decoded, form);
+
+        // The encoding is bound to the discriminant it was made for
+        let other = Discriminant::from_seed(b"HelloWorld!", 512).unwrap();
+        assert!(QuadraticForm::from_bytes(&other, form.as_bytes()).is_err());
+
+        assert!(matches!(
+            QuadraticForm::from_bytes(&discriminant, &[]),
+            Err(ChiavdfError::InvalidInput(_))
+        ));
+        let mut corrupted = form.into_bytes();
+        corrupted[10] ^= 1;
+        assert!(matches!(
+            QuadraticForm::from_bytes(&discriminant, &corrupted),
+            Err(ChiavdfError::InvalidForm(_))
+        ));
+    }
+
+    #[test]
+    fn test_coefficients() {
+        let discriminant = Discriminant::from_seed(b"HelloWorld", 512).unwrap();
+        let form = QuadraticForm::generator(&discriminant).pow(&BigUint::from(1234u32));
+
+        let (a, b, c) = form.coefficients();
+        assert_eq!(&b * &b - 4 * &a * &c, discriminant.to_bigint());
+        assert_eq!(
+            QuadraticForm::from_abc(&discriminant, &a, &b, &c).unwrap(),
+            form
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_mismatched_discriminants() {
+        let discriminant = Discriminant::from_seed(b"HelloWorld", 512).unwrap();
+        let other = Discriminant::from_seed(b"HelloWorld!", 512).unwrap();
+        let _ = QuadraticForm::generator(&discriminant) * QuadraticForm::generator(&other);
+    }
+}
@@ -1,5 +1,25 @@
+#[cfg(feature = "cpp")]
 pub mod accumulators;
+#[cfg(feature = "async")]
+pub mod async_bindings;
+pub mod bqfc;
+#[cfg(feature = "cpp")]
 pub mod c_bindings;
+pub mod class_group;
 pub mod constants;
+pub mod create_discriminant;
+pub mod discriminant;
+pub mod error;
 pub mod fastcrypto;
+#[cfg(feature = "cpp")]
+pub mod form;
+#[cfg(feature = "cpp")]
 pub mod hash;
+#[cfg(feature = "cpp")]
+pub mod pietrzak;
+mod pprods;
+mod primetest;
+mod proof_common;
+#[cfg(feature = "pure-rust")]
+pub mod verifier;
+mod xgcd_partial;
@@ -0,0 +1,146 @@
+//! Pietrzak proofs that y = x^(2^T), over the same discriminants and form encoding as the
+//! Wesolowski proofs of [crate::c_bindings].
+//!
+//! Each round of the proof halves T with one form, so a proof has one form per halving of T,
+//! about log2(T) of them. Unlike a Wesolowski proof, checking it needs no prime challenge, only
+//! exponentiations by 128-bit integers.
+
+use super::c_bindings::{bindings, check_form_size, last_error, take_byte_array};
+use super::discriminant::Discriminant;
+use super::error::{ChiavdfError, ChiavdfResult};
+
+/// The number of forms of a proof for `num_iterations` squarings.
+pub fn proof_forms(num_iterations: u64) -> usize {
+    let mut rounds = 0;
+    let mut t = num_iterations;
+    while t > 1 {
+        t = t.div_ceil(2);
+        rounds += 1;
+    }
+    rounds
+}
+
+/// Prove that `y_s` is `x_s` squared `num_iterations` times, from the intermediates returned by
+/// [crate::c_bindings::evaluate_to_prove] with the same arguments.
+pub fn prove(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    intermediates: &[u8],
+    num_iterations: u64,
+) -> ChiavdfResult<Vec<u8>> {
+    check_form_size(discriminant, x_s)?;
+    check_form_size(discriminant, y_s)?;
+    if !intermediates.len().is_multiple_of(discriminant.form_size()) {
+        return Err(ChiavdfError::InvalidInput(
+            "Intermediates must be a sequence of forms".to_string(),
+        ));
+    }
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns a null `data` and records the error.
+    unsafe {
+        let array = bindings::pietrzak_prove_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            x_s.as_ptr(),
+            y_s.as_ptr(),
+            x_s.len(),
+            intermediates.as_ptr(),
+            intermediates.len(),
+            num_iterations,
+        );
+        take_byte_array(array)
+    }
+}
+
+/// Verify a proof returned by [prove]. A proof of the wrong size is invalid.
+pub fn verify(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    proof: &[u8],
+    num_iterations: u64,
+) -> ChiavdfResult<bool> {
+    check_form_size(discriminant, x_s)?;
+    check_form_size(discriminant, y_s)?;
+    // SAFETY: The length of each individual array is passed in as to prevent buffer overflows.
+    // Exceptions are caught on the C++ side, which returns false and records the error.
+    let valid = unsafe {
+        bindings::pietrzak_verify_wrapper(
+            discriminant.as_bytes().as_ptr(),
+            discriminant.as_bytes().len(),
+            x_s.as_ptr(),
+            y_s.as_ptr(),
+            x_s.len(),
+            proof.as_ptr(),
+            proof.len(),
+            num_iterations,
+        )
+    };
+    match last_error() {
+        Some(error) if !valid => Err(error),
+        _ => Ok(valid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::c_bindings::{evaluate_to_prove, generator, power};
+
+    #[test]
+    fn test_pietrzak() {
+        let disc = Discriminant::from_seed(b"HelloWorld", 1024).unwrap();
+        let g = generator(&disc).unwrap();
+        let x = power(&disc, &g, b"pietrzak").unwrap();
+
+        // Even, odd and small numbers of iterations, with and without squarings from x
+        for num_iterations in [0, 1, 2, 3, 17, 1000, 4097, 100_000] {
+            let (y, intermediates) = evaluate_to_prove(&disc, &x, num_iterations).unwrap();
+            let proof = prove(&disc, &x, &y, &intermediates, num_iterations).unwrap();
+            assert_eq!(proof.len(), proof_forms(num_iterations) * disc.form_size());
+            assert_eq!(
+                verify(&disc, &x, &y, &proof, num_iterations),
+                Ok(true),
+                "{} iterations",
+                num_iterations
+            );
+            if num_iterations < 2 {
+                continue;
+            }
+
+            assert_eq!(verify(&disc, &x, &x, &proof, num_iterations), Ok(false));
+            assert_eq!(verify(&disc, &g, &y, &proof, num_iterations), Ok(false));
+            let mut swapped = proof.clone();
+            swapped.rotate_left(disc.form_size());
+            assert_eq!(
+                verify(&disc, &x, &y, &swapped, num_iterations),
+                Ok(proof.len() == disc.form_size())
+            );
+            assert_eq!(
+                verify(&disc, &x, &y, &proof[disc.form_size()..], num_iterations),
+                Ok(false)
+            );
+            let mut corrupted = proof.clone();
+            corrupted[20] ^= 1;
+            assert!(verify(&disc, &x, &y, &corrupted, num_iterations).is_err());
+        }
+
+        assert_eq!(proof_forms(1 << 20), 20);
+        assert_eq!(proof_forms((1 << 20) + 1), 21);
+
+        let (y, intermediates) = evaluate_to_prove(&disc, &x, 1000).unwrap();
+        assert_eq!(
+            verify(
+                &disc,
+                &x,
+                &y,
+                &prove(&disc, &x, &y, &intermediates, 1000).unwrap(),
+                1001
+            ),
+            Ok(false)
+        );
+        assert!(prove(&disc, &x, &y, &intermediates, 2000).is_err());
+        assert!(prove(&disc, &x, &y[1..], &intermediates, 1000).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+// Port of the strengthened Baillie-PSW primality test in src/primetest.h, which `integer::prime`
+// and therefore `HashPrime` and `CreateDiscriminant` rely on.
+
+use num_bigint::{BigInt, BigUint};
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+
+use super::fastcrypto::jacobi::jacobi;
+use super::pprods::PPRODS;
+
+const VPRP_MAX_D: i64 = 1000;
+
+/// Whether `n` is a probable prime, giving the same answer as `is_prime_bpsw` for every input:
+/// 1. GCDs of `n` with products of small primes, so small primes are reported composite as well.
+/// 2. A Miller-Rabin round with base 2.
+/// 3. A Lucas-V probable prime test.
+pub(crate) fn is_prime_bpsw(n: &BigUint) -> bool {
+    if n <= &BigUint::one() {
+        return false;
+    }
+    // Discard even numbers
+    if n.is_even() && n != &BigUint::from(2u32) {
+        return false;
+    }
+
+    // Fewer GCDs for numbers below 1024 bits, proportionally to the cube of their number of limbs
+    let limbs = n.bits().div_ceil(64) as usize;
+    let mut n_pprods = PPRODS.len();
+    if limbs < 16 {
+        const MIN_PPRODS: usize = 5;
+        n_pprods = MIN_PPRODS + (n_pprods - MIN_PPRODS) * limbs.pow(3) / 16usize.pow(3);
+    }
+    if PPRODS[..n_pprods]
+        .iter()
+        .any(|&pprod| (n % pprod).to_u64().unwrap().gcd(&pprod) != 1)
+    {
+        return false;
+    }
+
+    miller_rabin(n) && is_vprp(n)
+}
+
+/// One round of the Miller-Rabin test with base 2.
+fn miller_rabin(n: &BigUint) -> bool {
+    let n_minus_one = n - 1u32;
+    let s = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> s;
+
+    let mut b = BigUint::from(2u32).modpow(&d, n);
+    if b.is_one() {
+        return true;
+    }
+    for _ in 0..s {
+        if b == n_minus_one {
+            return true;
+        }
+        b = &b * &b % n;
+    }
+    false
+}
+
+/// Find the first `D` in 5, -7, 9, -11, ... with Jacobi symbol (D / n) = -1 and return the
+/// Lucas parameters (P, Q) for it, or `None` if there is none below [VPRP_MAX_D].
+fn find_pq(n: &BigInt) -> Option<(i64, i64)> {
+    let mut d = 5;
+    while d < VPRP_MAX_D {
+        let signed_d = if d % 4 == 1 { d } else { -d };
+        if jacobi(&BigInt::from(signed_d), n) == Some(-1) {
+            return Some(if signed_d == 5 {
+                (5, 5)
+            } else {
+                (1, (1 - signed_d) / 4)
+            });
+        }
+        d += 2;
+    }
+    None
+}
+
+/// The Lucas sequence element V_e(P, Q) modulo m, following the same steps as `find_lucas_v`. The
+/// result is not fully reduced.
+fn find_lucas_v(e: &BigInt, m: &BigInt, p: i64, q: i64) -> BigInt {
+    let mut u1 = BigInt::one();
+    let mut u2 = BigInt::from(p);
+
+    for i in (0..e.bits() - 1).rev() {
+        let mut tmp = &u2 * &u1;
+        u2 = &u2 * &u2;
+        u1 = &u1 * &u1;
+        if e.bit(i) {
+            u1 = &u2 - &u1 * q;
+            if p != 1 {
+                u2 *= p;
+            }
+            u2 += tmp * (-2 * q);
+        } else {
+            u2 -= &u1 * q;
+            tmp <<= 1;
+            u1 = if p != 1 { tmp - &u1 * p } else { tmp - &u1 };
+        }
+        u1 %= m;
+        u2 %= m;
+    }
+    (u2 << 1) - u1 * p
+}
+
+/// The Lucas-V probable prime test: V_{n+1} = 2Q (mod n).
+fn is_vprp(n: &BigUint) -> bool {
+    let n = BigInt::from(n.clone());
+    let Some((p, q)) = find_pq(&n) else {
+        return false;
+    };
+
+    let v = find_lucas_v(&(&n + 1), &n, p, q);
+    let mut v = v.mod_floor(&n);
+    if q.is_negative() && !v.is_zero() {
+        // Ceiling remainder, like `mpz_cdiv_r`
+        v -= &n;
+    }
+    v == BigInt::from(2 * q)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_prime::nt_funcs::is_prime;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    #[test]
+    fn test_is_prime_bpsw() {
+        // The GCDs with the products of small primes also reject the small primes themselves. Only
+        // the first five products, up to 233, are used for single limb numbers.
+        for n in [0u64, 1, 2, 3, 4, 9, 53, 233] {
+            assert!(!is_prime_bpsw(&BigUint::from(n)));
+        }
+        for n in [239u64, 14951, 1_000_000_007, (1 << 61) - 1] {
+            assert!(is_prime_bpsw(&BigUint::from(n)));
+        }
+        // A strong pseudoprime to the bases 2 to 23 without small factors fails the Lucas test
+        assert!(!is_prime_bpsw(&BigUint::from(3_825_123_056_546_413_051u64)));
+
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        for bits in [64, 264, 1024] {
+            for _ in 0..200 {
+                let mut bytes = vec![0u8; bits / 8];
+                rng.fill(&mut bytes[..]);
+                let mut n = BigUint::from_bytes_be(&bytes);
+                n.set_bit(bits as u64 - 1, true);
+                n.set_bit(0, true);
+                assert_eq!(is_prime_bpsw(&n), is_prime(&n, None).probably(), "{}", n);
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// Port of the helpers in src/proof_common.h and src/create_discriminant.h used by the verifier and
+// the discriminant and prime hashing.
+
+use num_bigint::BigUint;
+#[cfg(feature = "pure-rust")]
+use num_traits::Zero;
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "pure-rust")]
+use super::bqfc;
+#[cfg(feature = "pure-rust")]
+use super::class_group::Qfb;
+#[cfg(feature = "pure-rust")]
+use super::constants::B_BITS;
+#[cfg(feature = "pure-rust")]
+use super::error::{ChiavdfError, ChiavdfResult};
+use super::primetest::is_prime_bpsw;
+
+/// Hash a seed to an integer of at most `length` bits, like `HashInt`: a single SHA-256 of the
+/// seed, truncated to `length / 8` bytes. Lengths above 256 bits still give a 256-bit value.
+pub(crate) fn hash_int(seed: &[u8], length: usize) -> BigUint {
+    let hash = Sha256::digest(seed);
+    BigUint::from_bytes_be(&hash[..hash.len().min(length / 8)])
+}
+
+/// Generate a pseudoprime of `length` bits from a seed, like `HashPrime`: expand the seed plus a
+/// counter with SHA-256, set the bits in `bitmask` and the lowest bit, and repeat until the result
+/// passes [is_prime_bpsw].
+pub(crate) fn hash_prime(seed: &[u8], length: usize, bitmask: &[u64]) -> BigUint {
+    assert!(length.is_multiple_of(8));
+    let mut sprout = seed.to_vec();
+
+    loop {
+        let mut blob = Vec::with_capacity(length / 8);
+        while blob.len() < length / 8 {
+            // Increment sprout by 1
+            for byte in sprout.iter_mut().rev() {
+                *byte = byte.wrapping_add(1);
+                if *byte != 0 {
+                    break;
+                }
+            }
+            let hash = Sha256::digest(&sprout);
+            let take = hash.len().min(length / 8 - blob.len());
+            blob.extend_from_slice(&hash[..take]);
+        }
+
+        let mut p = BigUint::from_bytes_be(&blob);
+        for &bit in bitmask {
+            p.set_bit(bit, true);
+        }
+        // Force the number to be odd
+        p.set_bit(0, true);
+        if is_prime_bpsw(&p) {
+            return p;
+        }
+    }
+}
+
+/// The absolute value of the discriminant of `length` bits derived from a seed, like
+/// `CreateDiscriminant`. Setting the three lowest bits makes -D = 1 (mod 8).
+pub(crate) fn create_discriminant(seed: &[u8], length: usize) -> BigUint {
+    hash_prime(seed, length, &[0, 1, 2, length as u64 - 1])
+}
+
+/// The Fiat-Shamir challenge prime B for the proof that `y` is reached from `x`, like `GetB`.
+#[cfg(feature = "pure-rust")]
+pub(crate) fn get_b(d_bits: usize, x: &Qfb, y: &Qfb) -> ChiavdfResult<BigUint> {
+    let mut serialization = serialize_form(x, d_bits)?;
+    serialization.extend(serialize_form(y, d_bits)?);
+    Ok(hash_prime(&serialization, B_BITS, &[B_BITS as u64 - 1]))
+}
+
+/// Serialize a reduced form, like `SerializeForm`.
+#[cfg(feature = "pure-rust")]
+pub(crate) fn serialize_form(form: &Qfb, d_bits: usize) -> ChiavdfResult<Vec<u8>> {
+    bqfc::serialize(&form.a, &form.b, d_bits)
+}
+
+/// 2^exponent mod `modulus`, like `FastPow` with base 2.
+#[cfg(feature = "pure-rust")]
+pub(crate) fn fast_pow(exponent: u64, modulus: &BigUint) -> ChiavdfResult<BigUint> {
+    if modulus.is_zero() {
+        return Err(ChiavdfError::Exception("Division by 0".to_string()));
+    }
+    Ok(BigUint::from(2u32).modpow(&BigUint::from(exponent), modulus))
+}
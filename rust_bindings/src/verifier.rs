@@ -0,0 +1,313 @@
+//! Pure-Rust port of the Wesolowski verifier in src/verifier.h, available with the `pure-rust`
+//! feature. It does not need GMP or the C++ library and gives the same results as the C++
+//! `verify_wrapper` and `verify_n_wesolowski_wrapper`, including which inputs are errors.
+
+use num_bigint::{BigInt, BigUint};
+
+use super::class_group::{multiply, nucomp_bound, pow, Qfb};
+use super::constants::B_BYTES;
+use super::discriminant::Discriminant;
+use super::error::{ChiavdfError, ChiavdfResult};
+use super::proof_common::{fast_pow, get_b};
+
+/// Verify a Wesolowski proof that `y_s` is `x_s` squared `num_iterations` times.
+///
+/// `Ok(false)` means the proof is invalid, an error that one of the forms could not be decoded.
+pub fn verify(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    y_s: &[u8],
+    proof: &[u8],
+    num_iterations: u64,
+) -> ChiavdfResult<bool> {
+    if y_s.len() != x_s.len() || proof.len() != x_s.len() {
+        return Err(ChiavdfError::InvalidInput(
+            "All forms must have the same size".to_string(),
+        ));
+    }
+    let d = discriminant.to_bigint();
+    let x = Qfb::deserialize(&d, x_s)?;
+    let y = Qfb::deserialize(&d, y_s)?;
+    let proof = Qfb::deserialize(&d, proof)?;
+    verify_wesolowski_proof(&d, &x, &y, &proof, num_iterations)
+}
+
+/// Verify an N-Wesolowski proof blob: the final `y` and its proof, followed by `recursion`
+/// segments of `iterations (8 bytes, big-endian) || B || proof`, like
+/// `CheckProofOfTimeNWesolowski`. The segments are checked from the last one, which starts at
+/// `x_s`.
+pub fn verify_n_wesolowski(
+    discriminant: &Discriminant,
+    x_s: &[u8],
+    proof: &[u8],
+    num_iterations: u64,
+    recursion: u64,
+) -> ChiavdfResult<bool> {
+    // The C++ verifier takes the blob length and the depth as `int32_t`
+    let depth = recursion as i32 as i64;
+    let blob_len = proof.len() as i32 as i64;
+    let form_size = discriminant.form_size() as i64;
+    let segment_len = 8 + B_BYTES as i64 + form_size;
+    if depth < 0 || blob_len != 2 * form_size + depth * segment_len {
+        return Ok(false);
+    }
+
+    let d = discriminant.to_bigint();
+    let mut x = Qfb::deserialize(&d, x_s)?;
+    let mut iterations = num_iterations;
+
+    let form_size = form_size as usize;
+    let segment_len = segment_len as usize;
+    for segment in proof[2 * form_size..].rchunks_exact(segment_len) {
+        let segment_iters = u64::from_be_bytes(segment[..8].try_into().unwrap());
+        let b = BigUint::from_bytes_be(&segment[8..8 + B_BYTES]);
+        let segment_proof = Qfb::deserialize(&d, &segment[8 + B_BYTES..])?;
+        match verify_weso_segment(&d, &x, &segment_proof, &b, segment_iters)? {
+            Some(y) => x = y,
+            None => return Ok(false),
+        }
+        if segment_iters > iterations {
+            return Ok(false);
+        }
+        iterations -= segment_iters;
+    }
+
+    let y = Qfb::deserialize(&d, &proof[..form_size])?;
+    let final_proof = Qfb::deserialize(&d, &proof[form_size..2 * form_size])?;
+    verify_wesolowski_proof(&d, &x, &y, &final_proof, iterations)
+}
+
+/// Check `proof^B * x^(2^iterations mod B) == y`, like `VerifyWesolowskiProof`.
+fn verify_wesolowski_proof(
+    d: &BigInt,
+    x: &Qfb,
+    y: &Qfb,
+    proof: &Qfb,
+    iterations: u64,
+) -> ChiavdfResult<bool> {
+    let b = get_b(d.bits() as usize, x, y)?;
+    Ok(&evaluate_segment(d, x, proof, &b, iterations)? == y)
+}
+
+/// Recompute the output of a segment with challenge `b` and return it if `b` is the challenge
+/// for it, like `VerifyWesoSegment`.
+fn verify_weso_segment(
+    d: &BigInt,
+    x: &Qfb,
+    proof: &Qfb,
+    b: &BigUint,
+    iterations: u64,
+) -> ChiavdfResult<Option<Qfb>> {
+    let y = evaluate_segment(d, x, proof, b, iterations)?;
+    Ok((b == &get_b(d.bits() as usize, x, &y)?).then_some(y))
+}
+
+/// proof^B * x^(2^iterations mod B)
+fn evaluate_segment(
+    d: &BigInt,
+    x: &Qfb,
+    proof: &Qfb,
+    b: &BigUint,
+    iterations: u64,
+) -> ChiavdfResult<Qfb> {
+    let bound = nucomp_bound(d);
+    let r = fast_pow(iterations, b)?;
+    let f1 = pow(proof, b, d, &bound);
+    let f2 = pow(x, &r, d, &bound);
+    Ok(multiply(&f1, &f2, d, &bound))
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+    #[cfg(feature = "cpp")]
+    use crate::c_bindings;
+    #[cfg(feature = "cpp")]
+    use crate::form::QuadraticForm;
+
+    // 1000 squarings of the generator of the discriminant from the seed "pure rust verifier" of
+    // 512 bits, proven by the C++ library
+    const X: [u8; 52] = hex!("08000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+    const Y: [u8; 52] = hex!("0000dad2b1738cdf35130ff9839e0e34c0fd3dfa5b5da77d8410118114793c5db1245f891bb4f163d54b717924bc10b8db0a0100");
+    const PROOF: [u8; 52] = hex!("0300543d099e848386e76a3f3a8b00f5ea1e0d8463324b9e4e588ee83ee1b45aa901edef98ec39f74e804bb6fc483b73cd050500");
+    // `prove_n_wesolowski` of the same 1000 squarings with recursion 1
+    const BLOB: [u8; 197] = hex!("0000dad2b1738cdf35130ff9839e0e34c0fd3dfa5b5da77d8410118114793c5db1245f891bb4f163d54b717924bc10b8db0a01000300e91070f29cd2a304afa6278b5ada3db8d199ede78cbc239371f36fd6ba57260412a58aa94fc0b4e57becb2fa1b593c0002010000000000000258fef09e327950bf9c4dedf4754f2384e5f1d4f03b327cb19344fb307b8e9489284b00005671e925ea91746afb45522d3518da122ad057fec5ea0fe2286249d0b877d67769a7df6b6502cc6ac353a46f8619f46e0100");
+
+    #[test]
+    fn test_verify_fixed_proofs() {
+        let discriminant = Discriminant::from_seed(b"pure rust verifier", 512).unwrap();
+
+        assert_eq!(verify(&discriminant, &X, &Y, &PROOF, 1000), Ok(true));
+        assert_eq!(verify(&discriminant, &X, &Y, &PROOF, 999), Ok(false));
+        assert_eq!(verify(&discriminant, &X, &PROOF, &Y, 1000), Ok(false));
+        assert_eq!(verify(&discriminant, &X, &Y, &Y, 1000), Ok(false));
+        let mut tampered = PROOF;
+        tampered[20] ^= 0x10;
+        assert_eq!(
+            verify(&discriminant, &X, &Y, &tampered, 1000),
+            Err(ChiavdfError::InvalidForm(
+                "Deserializing compressed form failed".to_string()
+            ))
+        );
+        assert!(verify(&discriminant, &X, &Y, &PROOF[1..], 1000).is_err());
+
+        assert_eq!(
+            verify_n_wesolowski(&discriminant, &X, &BLOB, 1000, 1),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_n_wesolowski(&discriminant, &X, &BLOB, 1001, 1),
+            Ok(false)
+        );
+        assert_eq!(
+            verify_n_wesolowski(&discriminant, &X, &BLOB, 1000, 0),
+            Ok(false)
+        );
+        // The iterations of the segment
+        let mut tampered = BLOB;
+        tampered[2 * X.len() + 7] ^= 0x02;
+        assert_eq!(
+            verify_n_wesolowski(&discriminant, &X, &tampered, 1000, 1),
+            Ok(false)
+        );
+    }
+
+    /// Build an N-Wesolowski blob from `x` with one segment per entry of `segments` with the C++
+    /// prover, the last entry being the final proof.
+    #[cfg(feature = "cpp")]
+    fn n_wesolowski_blob(discriminant: &Discriminant, x_s: &[u8], segments: &[u64]) -> Vec<u8> {
+        let d = discriminant.to_bigint();
+        let mut x_s = x_s.to_vec();
+        let mut encoded_segments = Vec::new();
+        for &iterations in &segments[..segments.len() - 1] {
+            let (y_s, proof) =
+                c_bindings::evaluate_and_prove(discriminant, &x_s, iterations).unwrap();
+            let x = Qfb::deserialize(&d, &x_s).unwrap();
+            let y = Qfb::deserialize(&d, &y_s).unwrap();
+            let b = get_b(discriminant.bits(), &x, &y).unwrap().to_bytes_be();
+
+            let mut segment = iterations.to_be_bytes().to_vec();
+            segment.extend(vec![0; B_BYTES - b.len()]);
+            segment.extend(b);
+            segment.extend(proof);
+            encoded_segments.push(segment);
+            x_s = y_s;
+        }
+
+        let (y_s, proof) =
+            c_bindings::evaluate_and_prove(discriminant, &x_s, *segments.last().unwrap()).unwrap();
+        let mut blob = [y_s, proof].concat();
+        for segment in encoded_segments.iter().rev() {
+            blob.extend_from_slice(segment);
+        }
+        blob
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_matches_c_verify() {
+        for d_bits in [512, 1024] {
+            let discriminant = Discriminant::from_seed(b"pure rust verifier", d_bits).unwrap();
+            let x_s = QuadraticForm::generator(&discriminant).into_bytes();
+            for iterations in [1, 2, 17, 1000] {
+                let (y_s, proof) =
+                    c_bindings::evaluate_and_prove(&discriminant, &x_s, iterations).unwrap();
+                let expected = c_bindings::verify(&discriminant, &x_s, &y_s, &proof, iterations);
+                assert_eq!(expected, Ok(true));
+                assert_eq!(
+                    verify(&discriminant, &x_s, &y_s, &proof, iterations),
+                    expected
+                );
+
+                // Wrong number of iterations
+                for wrong in [iterations - 1, iterations + 1] {
+                    let expected = c_bindings::verify(&discriminant, &x_s, &y_s, &proof, wrong);
+                    assert_eq!(verify(&discriminant, &x_s, &y_s, &proof, wrong), expected);
+                }
+
+                // Corrupted forms are either rejected or decode to another form
+                for index in [0, 1, 2, 20, proof.len() - 1] {
+                    let mut corrupted = proof.clone();
+                    corrupted[index] ^= 0x10;
+                    let expected =
+                        c_bindings::verify(&discriminant, &x_s, &y_s, &corrupted, iterations);
+                    assert_eq!(
+                        verify(&discriminant, &x_s, &y_s, &corrupted, iterations),
+                        expected
+                    );
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "cpp")]
+    #[test]
+    fn test_matches_c_verify_n_wesolowski() {
+        let discriminant = Discriminant::from_seed(b"pure rust verifier", 512).unwrap();
+        let x_s = QuadraticForm::generator(&discriminant).into_bytes();
+
+        for segments in [vec![100], vec![40, 60], vec![10, 20, 70]] {
+            let total = segments.iter().sum();
+            let depth = segments.len() as u64 - 1;
+            let blob = n_wesolowski_blob(&discriminant, &x_s, &segments);
+
+            for (iterations, recursion) in [
+                (total, depth),
+                (total - 1, depth),
+                (total + 1, depth),
+                (total, depth + 1),
+                (5, depth),
+            ] {
+                let expected = c_bindings::verify_n_wesolowski(
+                    &discriminant,
+                    &x_s,
+                    &blob,
+                    iterations,
+                    recursion,
+                );
+                assert_eq!(
+                    expected,
+                    Ok(iterations == total && recursion == depth),
+                    "{:?}",
+                    (&segments, iterations, recursion)
+                );
+                assert_eq!(
+                    verify_n_wesolowski(&discriminant, &x_s, &blob, iterations, recursion),
+                    expected
+                );
+            }
+
+            // Tamper with the segment iterations, B and the proofs
+            let form_size = discriminant.form_size();
+            for index in [0, 2 * form_size + 7, 2 * form_size + 8 + 32, blob.len() - 5] {
+                if index >= blob.len() {
+                    continue;
+                }
+                let mut corrupted = blob.clone();
+                corrupted[index] ^= 0x02;
+                let expected =
+                    c_bindings::verify_n_wesolowski(&discriminant, &x_s, &corrupted, total, depth);
+                assert_eq!(
+                    verify_n_wesolowski(&discriminant, &x_s, &corrupted, total, depth),
+                    expected
+                );
+            }
+        }
+
+        // A zero B is reported like the C++ exception
+        let mut blob = n_wesolowski_blob(&discriminant, &x_s, &[40, 60]);
+        let b_offset = 2 * discriminant.form_size() + 8;
+        blob[b_offset..b_offset + B_BYTES].fill(0);
+        let expected = c_bindings::verify_n_wesolowski(&discriminant, &x_s, &blob, 100, 1);
+        assert_eq!(
+            expected,
+            Err(ChiavdfError::Exception("Division by 0".to_string()))
+        );
+        assert_eq!(
+            verify_n_wesolowski(&discriminant, &x_s, &blob, 100, 1),
+            expected
+        );
+    }
+}
@@ -0,0 +1,88 @@
+// Port of `mpz_xgcd_partial` in src/xgcd_partial.c, used by the form compression and NUCOMP.
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+/// Run the extended Euclidean algorithm on `a` and `b` until the remainder `r1` is at most
+/// `bound`, like `mpz_xgcd_partial` with 64-bit limbs. The Lehmer steps can take one more quotient
+/// than the plain extended Euclidean algorithm stopping at `bound` would, so they are reproduced
+/// exactly to get the same form encodings.
+///
+/// Returns the cofactors `co2`, `co1` and the last remainder `r1`, which satisfy
+/// `co2 * r1 - co1 * r2 = ±a` with the previous remainder `r2`.
+pub(crate) fn xgcd_partial(a: &BigInt, b: &BigInt, bound: &BigInt) -> (BigInt, BigInt, BigInt) {
+    const LIMB_BITS: u64 = 64;
+
+    let (mut r2, mut r1) = (a.clone(), b.clone());
+    let (mut co2, mut co1) = (BigInt::zero(), -BigInt::one());
+
+    // The low limb of `value >> shift`, like `mpz_get_ui` after `mpz_tdiv_q_2exp`
+    let low_limb = |value: &BigInt, shift: u64| -> i64 {
+        (value.magnitude() >> shift)
+            .iter_u64_digits()
+            .next()
+            .unwrap_or(0) as i64
+    };
+
+    while !r1.is_zero() && &r1 > bound {
+        let shift = (r2.bits().max(r1.bits()) + 1).saturating_sub(LIMB_BITS);
+        let mut rr2 = low_limb(&r2, shift);
+        let mut rr1 = low_limb(&r1, shift);
+        let bb = low_limb(bound, shift);
+
+        let (mut aa2, mut aa1, mut bb2, mut bb1) = (0i64, 1i64, 1i64, 0i64);
+        let mut i = 0;
+        while rr1 != 0 && rr1 > bb {
+            let qq = rr2 / rr1;
+
+            let t1 = rr2.wrapping_sub(qq.wrapping_mul(rr1));
+            let t2 = aa2.wrapping_sub(qq.wrapping_mul(aa1));
+            let t3 = bb2.wrapping_sub(qq.wrapping_mul(bb1));
+
+            let stop = if i & 1 == 1 {
+                t1 < t3.wrapping_neg() || rr1.wrapping_sub(t1) < t2.wrapping_sub(aa1)
+            } else {
+                t1 < t2.wrapping_neg() || rr1.wrapping_sub(t1) < t3.wrapping_sub(bb1)
+            };
+            if stop {
+                break;
+            }
+
+            (rr2, rr1) = (rr1, t1);
+            (aa2, aa1) = (aa1, t2);
+            (bb2, bb1) = (bb1, t3);
+            i += 1;
+        }
+
+        if i == 0 {
+            let (q, r) = r2.div_mod_floor(&r1);
+            r2 = std::mem::replace(&mut r1, r);
+            let co = &co2 - &co1 * q;
+            co2 = std::mem::replace(&mut co1, co);
+        } else {
+            let r = &r2 * bb2 + &r1 * aa2;
+            r1 = &r1 * aa1 + &r2 * bb1;
+            r2 = r;
+
+            let co = &co2 * bb2 + &co1 * aa2;
+            co1 = &co1 * aa1 + &co2 * bb1;
+            co2 = co;
+
+            if r1.is_negative() {
+                co1 = -co1;
+                r1 = -r1;
+            }
+            if r2.is_negative() {
+                co2 = -co2;
+                r2 = -r2;
+            }
+        }
+    }
+
+    if r2.is_negative() {
+        co2 = -co2;
+        co1 = -co1;
+    }
+    (co2, co1, r1)
+}
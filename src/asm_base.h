@@ -81,7 +81,7 @@ string track_asm(string comment, string jump_to = "") {
     APPEND_M(str( "MOV RAX, [asm_tracking_data+#]", to_hex(8*(id-1)) ));
     APPEND_M(str( "LEA RAX, [RAX+1]" ));
     APPEND_M(str( "MOV [asm_tracking_data+#], RAX", to_hex(8*(id-1)) ));
-#ifdef CHIAOSX
+#ifdef CHIA_ASM_PIC
     APPEND_M(str( "LEA RAX, [RIP+comment_label] " ));
 #else
     APPEND_M(str( "MOV RAX, OFFSET FLAT:#", comment_label ));
@@ -116,7 +116,7 @@ string constant_address_uint64(uint64 value_bits_0, uint64 value_bits_1, bool us
         APPEND_M(str( ".quad #", to_hex(value_bits_1) )); //lane 1
         APPEND_M(str( ".text" ));
     }
-#ifdef CHIAOSX
+#ifdef CHIA_ASM_PIC
     return (use_brackets)? str( "[RIP+#]", name ) : name;
 #else
     return (use_brackets)? str( "[#]", name ) : name;
@@ -148,7 +148,7 @@ string constant_address_avx512_uint64(array<uint64, 8> value, bool use_brackets=
         }
         APPEND_M(str( ".text" ));
     }
-#ifdef CHIAOSX
+#ifdef CHIA_ASM_PIC
     return (use_brackets)? str( "ZMMWORD PTR [RIP+#]", name ) : name;
 #else
     return (use_brackets)? str( "ZMMWORD PTR [#]", name ) : name;
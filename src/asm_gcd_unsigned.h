@@ -450,7 +450,7 @@ void gcd_unsigned(
         APPEND_M(str( ".balign 8" ));
         APPEND_M(str( "#:", jump_table_label ));
 
-#ifdef CHIAOSX
+#ifdef CHIA_ASM_PIC
         APPEND_M(str( ".text" ));
 
         APPEND_M(str( "MOV `tmp, `spill_a_end_index" ));
@@ -16,9 +16,12 @@ struct qfb_c {
 };
 
 #define BQFC_MAX_D_BITS 4096
-/* Force all forms to have the same size (100 bytes). */
+/* Size of the forms of the largest discriminants, an upper bound for all of them. */
 #define BQFC_FORM_SIZE ((BQFC_MAX_D_BITS + 31) / 32 * 3 + 4)
 
+/* Size of the serialized forms of a discriminant of d_bits bits. */
+int bqfc_get_compr_size(size_t d_bits);
+
 int bqfc_compr(struct qfb_c *out_c, mpz_t a, mpz_t b);
 
 int bqfc_decompr(mpz_t out_a, mpz_t out_b, const mpz_t D, const struct qfb_c *c);
@@ -3,10 +3,124 @@
 #include <gmpxx.h>
 #include "../verifier.h"
 #include "../prover_slow.h"
+#include "../pietrzak.h"
+#ifdef CHIAVDFC_TIMELORD
+#include "../vdf.h"
+#include "../prover_session.h"
+#include <mutex>
+#include <thread>
+#endif
 #include <cmath>
+#include <atomic>
+#include <new>
+#include <string>
+
+namespace {
+    // Details of the last exception caught by a wrapper on the calling thread
+    thread_local int last_error_kind = CHIAVDF_ERROR_NONE;
+    thread_local std::string last_error_message;
+
+    void set_last_error(int kind, const char* message) {
+        last_error_kind = kind;
+        last_error_message = message;
+    }
+
+    // Whether a blob of `proof_blob_size` bytes can hold `recursion` segments and `forms` forms of
+    // `form_size` bytes, within the int32_t lengths of the verifier
+    bool has_blob_size(size_t proof_blob_size, uint64_t recursion, size_t forms, size_t form_size) {
+        if (proof_blob_size > INT32_MAX || recursion > INT32_MAX) {
+            return false;
+        }
+        return proof_blob_size == forms * form_size + recursion * (8 + B_bytes + form_size);
+    }
+
+    // Record the exception being handled, must be called from within a catch block
+    void record_exception() {
+        try {
+            throw;
+        } catch (const std::bad_alloc& e) {
+            set_last_error(CHIAVDF_ERROR_BAD_ALLOC, e.what());
+        } catch (const std::exception& e) {
+            set_last_error(CHIAVDF_ERROR_EXCEPTION, e.what());
+        } catch (...) {
+            set_last_error(CHIAVDF_ERROR_UNKNOWN, "unknown exception");
+        }
+    }
+
+    // Hooks for the squaring loop of an evaluation of `num_iterations` squarings from the hooks of
+    // a wrapper, which may be null. The flag shared by the caller is viewed as the atomic it is.
+    // `reported` is the number of intermediates the caller already has.
+    SlowEvalHooks make_hooks(const EvalHooks* c_hooks, const integer& D, uint64_t num_iterations, size_t reported) {
+        static_assert(sizeof(std::atomic<bool>) == sizeof(bool), "atomic<bool> must have the layout of bool");
+        static_assert(std::atomic<bool>::is_always_lock_free, "atomic<bool> must be lock free");
+        SlowEvalHooks hooks;
+        if (c_hooks == nullptr) {
+            return hooks;
+        }
+        EvalHooks h = *c_hooks;
+        hooks.cancelled = reinterpret_cast<const std::atomic<bool>*>(h.cancelled);
+        if (h.progress != nullptr) {
+            hooks.progress = [=](uint64_t iterations) { return h.progress(h.context, iterations); };
+            hooks.progress_interval = h.progress_interval;
+        }
+        if (h.checkpoint != nullptr) {
+            int d_bits = D.num_bits();
+            int k, l;
+            GetSlowParameters(num_iterations, k, l);
+            hooks.checkpoint = [=](uint64_t iterations, form& y, const std::vector<form>& intermediates) mutable {
+                std::vector<uint8_t> y_s = SerializeForm(y, d_bits);
+                std::vector<uint8_t> inter_s;
+                for (size_t i = reported; i < intermediates.size(); i++) {
+                    form inter = intermediates[i];
+                    std::vector<uint8_t> inter_ser = SerializeForm(inter, d_bits);
+                    inter_s.insert(inter_s.end(), inter_ser.begin(), inter_ser.end());
+                }
+                reported = intermediates.size();
+                return h.checkpoint(h.context, iterations, k, l, y_s.data(), y_s.size(), inter_s.data(), inter_s.size());
+            };
+            hooks.checkpoint_interval = h.checkpoint_interval;
+        }
+        return hooks;
+    }
+}
+
+#ifdef CHIAVDFC_TIMELORD
+// Defined by the programs of the fast path, and raised on machines with AVX2
+int gcd_base_bits = 50;
+int gcd_128_max_iter = 3;
+
+namespace {
+    // Set up the fast path like the main of vdf_client does, before it is first used, without its
+    // status lines on stdout
+    void init_fast_path() {
+        static std::once_flag once;
+        std::call_once(once, [] {
+            quiet_mode = true;
+            init_gmp();
+            if (hasAVX2()) {
+                gcd_base_bits = 63;
+                gcd_128_max_iter = 2;
+            }
+        });
+    }
+}
 
+struct ProverSession {
+    FastProverSession session;
+
+    ProverSession(integer& D, form& x) : session(D, x) {}
+};
+#endif
 
 extern "C" {
+    int chiavdf_last_error_kind() {
+        return last_error_kind;
+    }
+
+    const char* chiavdf_last_error_message() {
+        return last_error_message.c_str();
+    }
+
     // C wrapper function
     bool create_discriminant_wrapper(const uint8_t* seed, size_t seed_size, size_t size_bits, uint8_t* result) {
         try {
@@ -15,13 +129,14 @@ extern "C" {
             mpz_export(result, NULL, 1, 1, 0, 0, discriminant.impl);
             return true;
         } catch (...) {
+            record_exception();
             return false;
         }
     }
 
     // Evaluate x^2^T and compute a Wesolowski proof quickly
     // Return evaluation and proof bundled
-    ByteArray prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size, uint64_t num_iterations) {
+    ByteArray prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size, uint64_t num_iterations, const EvalHooks* hooks) {
         try {
             integer discriminant;
             mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
@@ -29,7 +144,11 @@ extern "C" {
 
             form x = DeserializeForm(discriminant, x_s, form_size);
 
-            std::vector<uint8_t> result = ProveSlow(discriminant, x, num_iterations, "");
+            std::vector<uint8_t> result = ProveSlow(discriminant, x, num_iterations, "", make_hooks(hooks, discriminant, num_iterations, 0));
+            if (result.empty()) {
+                set_last_error(CHIAVDF_ERROR_CANCELLED, "Cancelled");
+                return ByteArray { nullptr, 0 };
+            }
 
             // Allocate memory for the result and copy data
             uint8_t* resultData = new uint8_t[result.size()];
@@ -37,13 +156,14 @@ extern "C" {
 
             return ByteArray  { resultData, result.size() };
         } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
 
     // Compute the evaluation x^2^T while storing found intermediate values
     // Return the evaluation and the intermediates values bundled
-    ByteArray evaluate_to_prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size, uint64_t num_iterations) {
+    ByteArray evaluate_to_prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size, uint64_t num_iterations, const EvalHooks* hooks) {
         try {
             integer discriminant;
             mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
@@ -51,7 +171,54 @@ extern "C" {
 
             form x = DeserializeForm(discriminant, x_s, form_size);
 
-            std::vector<uint8_t> result = EvalSlow(discriminant, x, num_iterations, "");
+            std::vector<uint8_t> result = EvalSlow(discriminant, x, num_iterations, "", make_hooks(hooks, discriminant, num_iterations, 0));
+            if (result.empty()) {
+                set_last_error(CHIAVDF_ERROR_CANCELLED, "Cancelled");
+                return ByteArray { nullptr, 0 };
+            }
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+
+    // Continue the evaluation of evaluate_to_prove_wrapper from a checkpoint
+    // Return the evaluation and the intermediates values bundled
+    ByteArray resume_evaluation_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* y_s, size_t form_size, const uint8_t* inter_s, size_t intermediates_size, uint64_t iterations_done, uint64_t num_iterations, int k, int l, const EvalHooks* hooks) {
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            int expected_k, expected_l;
+            GetSlowParameters(num_iterations, expected_k, expected_l);
+            if (k != expected_k || l != expected_l) {
+                throw std::invalid_argument("Checkpoint does not match the evaluation parameters");
+            }
+
+            form y = DeserializeForm(discriminant, y_s, form_size);
+            if (intermediates_size % form_size != 0) {
+                throw std::invalid_argument("Intermediates must be a sequence of forms");
+            }
+            size_t nb_intermediates = intermediates_size / form_size;
+            std::vector<form> intermediates;
+            intermediates.reserve(nb_intermediates);
+            for (size_t i = 0; i < nb_intermediates; i++) {
+                intermediates.push_back(DeserializeForm(discriminant, &inter_s[i * form_size], form_size));
+            }
+
+            std::vector<uint8_t> result = ResumeEvalSlow(discriminant, y, iterations_done, num_iterations, std::move(intermediates), "",
+                                                         make_hooks(hooks, discriminant, num_iterations, nb_intermediates));
+            if (result.empty()) {
+                set_last_error(CHIAVDF_ERROR_CANCELLED, "Cancelled");
+                return ByteArray { nullptr, 0 };
+            }
 
             // Allocate memory for the result and copy data
             uint8_t* resultData = new uint8_t[result.size()];
@@ -59,6 +226,33 @@ extern "C" {
 
             return ByteArray  { resultData, result.size() };
         } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+
+    ByteArray evaluate_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t x_s_size, uint64_t num_iterations, const EvalHooks* hooks) {
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            form x = DeserializeForm(discriminant, x_s, x_s_size);
+            SlowEvalHooks eval_hooks = make_hooks(hooks, discriminant, num_iterations, 0);
+            eval_hooks.checkpoint = nullptr;
+            std::vector<uint8_t> result = EvalOnlySlow(discriminant, x, num_iterations, "", eval_hooks);
+            if (result.empty()) {
+                set_last_error(CHIAVDF_ERROR_CANCELLED, "Cancelled");
+                return ByteArray { nullptr, 0 };
+            }
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
@@ -89,13 +283,186 @@ extern "C" {
 
             return ByteArray  { resultData, result.size() };
         } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
 
+    ByteArray pietrzak_prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, const uint8_t* inter_s, size_t intermediates_size, uint64_t num_iterations) {
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            form x = DeserializeForm(discriminant, x_s, form_size);
+            form y = DeserializeForm(discriminant, y_s, form_size);
+            auto intermediate = [&](size_t i) { return DeserializeForm(discriminant, &inter_s[i * form_size], form_size); };
+
+            std::vector<uint8_t> result = ProvePietrzak(discriminant, x, y, intermediate, intermediates_size / form_size, num_iterations);
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+
+    bool pietrzak_verify_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, const uint8_t* proof_s, size_t proof_size, uint64_t num_iterations) {
+        set_last_error(CHIAVDF_ERROR_NONE, "");
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            if (proof_size != GetPietrzakRounds(num_iterations) * form_size) {
+                return false;
+            }
+            form x = DeserializeForm(discriminant, x_s, form_size);
+            form y = DeserializeForm(discriminant, y_s, form_size);
+            std::vector<form> mus;
+            for (size_t i = 0; i < proof_size / form_size; i++) {
+                mus.push_back(DeserializeForm(discriminant, &proof_s[i * form_size], form_size));
+            }
+
+            return VerifyPietrzak(discriminant, x, y, mus, num_iterations);
+        } catch (...) {
+            record_exception();
+            return false;
+        }
+    }
+
+#ifdef CHIAVDFC_TIMELORD
+    // Prove x to x^2^T as an N-Wesolowski proof with `recursion` extra segments, with the
+    // TwoWesolowskiCallback and ProveTwoWeso of vdf_client on the fast squaring loop
+    // Return the proof blob checked by verify_n_wesolowski_wrapper
+    ByteArray prove_n_wesolowski_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size, uint64_t num_iterations, uint64_t recursion) {
+        try {
+            init_fast_path();
+
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            form x = DeserializeForm(discriminant, x_s, form_size);
+
+            // repeated_square gives up a little before kMaxItersAllowed
+            if (num_iterations + checkpoint_interval >= kMaxItersAllowed - 500000) {
+                throw std::invalid_argument("Too many iterations for the N-Wesolowski prover");
+            }
+            // ProveTwoWeso gives each extra segment two thirds of the remaining squarings, rounded
+            // down to a multiple of 100
+            uint64_t remaining = num_iterations;
+            for (uint64_t i = 0; i < recursion; i++) {
+                uint64_t iterations = remaining * 2 / 3;
+                iterations -= iterations % 100;
+                if (iterations == 0) {
+                    throw std::invalid_argument("Not enough iterations for the recursion depth");
+                }
+                remaining -= iterations;
+            }
+
+            integer L = root(-discriminant, 4);
+            TwoWesolowskiCallback weso(discriminant, x, num_iterations);
+            std::atomic<bool> stopped(false);
+            std::thread vdf_worker(repeated_square, num_iterations, x, std::ref(discriminant), std::ref(L), &weso, nullptr, std::ref(stopped));
+            Proof proof;
+            try {
+                // ProveTwoWeso adds segments until its depth reaches 2
+                proof = ProveTwoWeso(discriminant, x, num_iterations, 0, &weso, 2 - (int)recursion, stopped);
+            } catch (...) {
+                stopped = true;
+                vdf_worker.join();
+                throw;
+            }
+            stopped = true;
+            vdf_worker.join();
+
+            std::vector<uint8_t> result = proof.y;
+            VectorAppend(result, proof.proof);
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+#endif
+
+#ifdef CHIAVDFC_TIMELORD
+    ProverSession* prover_session_start(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size) {
+        try {
+            init_fast_path();
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            form x = DeserializeForm(discriminant, x_s, form_size);
+            return new ProverSession(discriminant, x);
+        } catch (...) {
+            record_exception();
+            return nullptr;
+        }
+    }
+
+    uint64_t prover_session_iterations(const ProverSession* session) {
+        return session->session.Iterations();
+    }
+
+    ByteArray prover_session_prove(ProverSession* session, uint64_t num_iterations, uint8_t* witness_type) {
+        try {
+            Proof proof = session->session.Prove(num_iterations);
+            if (proof.y.empty()) {
+                set_last_error(CHIAVDF_ERROR_CANCELLED, "Cancelled");
+                return ByteArray { nullptr, 0 };
+            }
+            std::vector<uint8_t> result = proof.y;
+            VectorAppend(result, proof.proof);
+            *witness_type = proof.witness_type;
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+
+    bool prover_session_stop(ProverSession* session) {
+        try {
+            session->session.Stop();
+            return true;
+        } catch (...) {
+            record_exception();
+            return false;
+        }
+    }
+
+    void prover_session_free(ProverSession* session) {
+        try {
+            session->session.Stop();
+        } catch (...) {
+            // The threads may still use the session, so it is leaked rather than destroyed
+            record_exception();
+            return;
+        }
+        delete session;
+    }
+#endif
+
     // Compute the Wesolowski proof naively with a double and add method
     // Return a Wesolowski proof
-    ByteArray prove_only_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size,  uint64_t num_iterations) {
+    ByteArray prove_only_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size,  uint64_t num_iterations, const bool* cancelled) {
         try {
             PulmarkReducer reducer;
 
@@ -117,7 +484,12 @@ extern "C" {
             mpz_fdiv_q(power_iterations.impl, power_iterations.impl, B.impl);
 
             // Computing proof x^floor((2^T) / l) using double and add method
-            form res = FastPowFormNucomp(x, discriminant, power_iterations, L, reducer);
+            const std::atomic<bool>* stop = reinterpret_cast<const std::atomic<bool>*>(cancelled);
+            form res = FastPowFormNucomp(x, discriminant, power_iterations, L, reducer, stop);
+            if (stop != nullptr && stop->load(std::memory_order_relaxed)) {
+                set_last_error(CHIAVDF_ERROR_CANCELLED, "Cancelled");
+                return ByteArray { nullptr, 0 };
+            }
             std::vector<uint8_t> result = SerializeForm(res, discriminant.num_bits());
 
             // Allocate memory for the result and copy data
@@ -126,6 +498,7 @@ extern "C" {
 
             return ByteArray  { resultData, result.size() };
         } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
@@ -133,6 +506,8 @@ extern "C" {
     // Verify a Wesolowski proof
     // Return true if valid, false otherwise
     bool verify_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, const uint8_t* proof_s, size_t form_size, uint64_t num_iterations) {
+        // false is also returned for invalid proofs, the caller tells both apart with the error kind
+        set_last_error(CHIAVDF_ERROR_NONE, "");
         try {
            integer discriminant;
             mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
@@ -147,12 +522,41 @@ extern "C" {
 
             return is_valid;
         } catch (...) {
+            record_exception();
+            return false;
+        }
+    }
+
+    bool verify_batch_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* proofs_s, size_t form_size, const uint64_t* num_iterations, const uint8_t* weights, size_t weight_size, size_t count) {
+        set_last_error(CHIAVDF_ERROR_NONE, "");
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            std::vector<form> xs, ys, proofs;
+            std::vector<uint64_t> iters(num_iterations, num_iterations + count);
+            std::vector<integer> weight_values;
+            for (size_t i = 0; i < count; i++) {
+                const uint8_t* proof_s = &proofs_s[3 * i * form_size];
+                xs.push_back(DeserializeForm(discriminant, proof_s, form_size));
+                ys.push_back(DeserializeForm(discriminant, &proof_s[form_size], form_size));
+                proofs.push_back(DeserializeForm(discriminant, &proof_s[2 * form_size], form_size));
+                integer weight;
+                mpz_import(weight.impl, weight_size, 1, 1, 0, 0, &weights[i * weight_size]);
+                weight_values.push_back(weight);
+            }
+
+            return VerifyWesolowskiBatch(discriminant, xs, ys, proofs, iters, weight_values);
+        } catch (...) {
+            record_exception();
             return false;
         }
     }
 
     // Verify a cascade of VDF evaluation and proofs
     bool verify_n_wesolowski_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* proof_blob, size_t proof_blob_size, uint64_t num_iterations, uint64_t recursion) {
+        set_last_error(CHIAVDF_ERROR_NONE, "");
         try {
             integer discriminant;
             mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
@@ -168,10 +572,105 @@ extern "C" {
                 recursion
             );
         } catch (...) {
+            record_exception();
             return false;
         }
     }
 
+    bool create_discriminant_and_verify_n_wesolowski_wrapper(const uint8_t* challenge, size_t challenge_size, size_t disc_size_bits, const uint8_t* x_s, size_t x_s_size, const uint8_t* proof_blob, size_t proof_blob_size, uint64_t num_iterations, uint64_t recursion) {
+        set_last_error(CHIAVDF_ERROR_NONE, "");
+        try {
+            if (disc_size_bits == 0 || disc_size_bits > BQFC_MAX_D_BITS) {
+                throw std::invalid_argument("Unsupported discriminant size");
+            }
+            size_t form_size = bqfc_get_compr_size(disc_size_bits);
+            if (x_s_size != form_size) {
+                throw std::invalid_argument("Forms must have the size of the discriminant");
+            }
+            if (!has_blob_size(proof_blob_size, recursion, 2, form_size)) {
+                return false;
+            }
+
+            std::vector<uint8_t> seed(challenge, challenge + challenge_size);
+            return CreateDiscriminantAndCheckProofOfTimeNWesolowski(
+                seed,
+                disc_size_bits,
+                x_s,
+                proof_blob,
+                proof_blob_size,
+                num_iterations,
+                recursion
+            );
+        } catch (...) {
+            record_exception();
+            return false;
+        }
+    }
+
+    bool verify_n_wesolowski_with_b_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* b_bytes, size_t b_size, const uint8_t* x_s, size_t x_s_size, const uint8_t* proof_blob, size_t proof_blob_size, uint64_t num_iterations, uint64_t recursion, uint8_t* y_s, size_t y_s_size) {
+        set_last_error(CHIAVDF_ERROR_NONE, "");
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            size_t form_size = bqfc_get_compr_size(discriminant.num_bits());
+            if (x_s_size != form_size || y_s_size != form_size) {
+                throw std::invalid_argument("Forms must have the size of the discriminant");
+            }
+            if (!has_blob_size(proof_blob_size, recursion, 1, form_size)) {
+                return false;
+            }
+
+            integer B;
+            mpz_import(B.impl, b_size, 1, 1, 0, 0, b_bytes);
+            std::pair<bool, std::vector<uint8_t>> result = CheckProofOfTimeNWesolowskiWithB(
+                discriminant,
+                B,
+                x_s,
+                proof_blob,
+                proof_blob_size,
+                num_iterations,
+                recursion
+            );
+            if (result.first) {
+                std::copy(result.second.begin(), result.second.end(), y_s);
+            }
+            return result.first;
+        } catch (...) {
+            record_exception();
+            return false;
+        }
+    }
+
+    ByteArray get_b_from_n_wesolowski_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t x_s_size, const uint8_t* proof_blob, size_t proof_blob_size, uint64_t num_iterations, uint64_t recursion) {
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            size_t form_size = bqfc_get_compr_size(discriminant.num_bits());
+            if (x_s_size != form_size) {
+                throw std::invalid_argument("Forms must have the size of the discriminant");
+            }
+            if (!has_blob_size(proof_blob_size, recursion, 2, form_size)) {
+                throw std::invalid_argument("The proof blob size does not match the recursion");
+            }
+
+            integer B = GetBFromProof(discriminant, x_s, proof_blob, proof_blob_size, num_iterations, recursion);
+            std::vector<uint8_t> result = B.to_bytes();
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+
     void delete_byte_array(ByteArray array) {
         delete[] array.data;
     }
@@ -199,6 +698,68 @@ extern "C" {
 
             return ByteArray  { resultData, result.size() };
         } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+
+    // Return the reduced form from discriminant, a and a signed b
+    ByteArray from_ab_reduced(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* a_bytes, size_t a_size, const uint8_t* b_bytes, size_t b_size, bool b_negative) {
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            integer a;
+            mpz_import(a.impl, a_size, 1, 1, 0, 0, a_bytes);
+
+            integer b;
+            mpz_import(b.impl, b_size, 1, 1, 0, 0, b_bytes);
+            if (b_negative)
+                b = -b;
+
+            // from_abd reduces the form, which only leaves (a, b) unchanged if it was reduced
+            form x = form::from_abd(a, b, discriminant);
+            if (mpz_cmp(x.a.impl, a.impl) || mpz_cmp(x.b.impl, b.impl)) {
+                throw std::runtime_error("Form is not reduced");
+            }
+
+            std::vector<uint8_t> result = SerializeForm(x, discriminant.num_bits());
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+
+    // Return the coefficients of a form: one byte set to 1 if b is negative, followed by
+    // |a|, |b| and |c| each exported as discriminant_size big endian bytes
+    ByteArray decompress_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size) {
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            form x = DeserializeForm(discriminant, x_s, form_size);
+
+            // Reduced forms have |b| <= a <= c <= |D|, so every coefficient fits
+            size_t result_size = 1 + 3 * discriminant_size;
+            uint8_t* resultData = new uint8_t[result_size]();
+            resultData[0] = mpz_sgn(x.b.impl) < 0 ? 1 : 0;
+            const integer* coefficients[3] = { &x.a, &x.b, &x.c };
+            for (int i = 0; i < 3; i++) {
+                size_t size = (mpz_sizeinbase(coefficients[i]->impl, 2) + 7) / 8;
+                mpz_export(&resultData[1 + (i + 1) * discriminant_size - size], NULL, 1, 1, 0, 0, coefficients[i]->impl);
+            }
+
+            return ByteArray  { resultData, result_size };
+        } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
@@ -220,6 +781,7 @@ extern "C" {
 
             return ByteArray  {resultData, result.size() };
         } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
@@ -241,6 +803,7 @@ extern "C" {
 
             return ByteArray  {resultData, result.size() };
         } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
@@ -269,6 +832,7 @@ extern "C" {
 
             return ByteArray  { resultData, result.size() };
         } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
@@ -292,6 +856,7 @@ extern "C" {
 
             return ByteArray  { resultData, result.size() };
         } catch (...) {
+            record_exception();
             return ByteArray { nullptr, 0 };
         }
     }
@@ -305,6 +870,7 @@ extern "C" {
             mpz_export(result, NULL, 1, 1, 0, 0, output.impl);
             return true;
         } catch (...) {
+            record_exception();
             return false;
         }
     }
@@ -318,7 +884,59 @@ extern "C" {
             mpz_export(result, NULL, 1, 1, 0, 0, output.impl);
             return true;
         } catch (...) {
+            record_exception();
             return false;
         }
     }
+
+    // Return the prime B = GetB(D, x, y) challenging a proof from x to y
+    ByteArray challenge_prime_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size) {
+        try {
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            form x = DeserializeForm(discriminant, x_s, form_size);
+            form y = DeserializeForm(discriminant, y_s, form_size);
+            std::vector<uint8_t> result = GetB(discriminant, x, y).to_bytes();
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
+
+    // Return HashInt of the serializations of x and y with int_length bits, without leading zeros
+    ByteArray challenge_int_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, size_t int_length) {
+        try {
+            // HashInt takes the first bytes of a single SHA256
+            if (int_length == 0 || int_length % 8 != 0 || int_length > 256) {
+                throw std::invalid_argument("The length must be a multiple of 8 of at most 256 bits");
+            }
+            integer discriminant;
+            mpz_import(discriminant.impl, discriminant_size, 1, 1, 0, 0, discriminant_bytes);
+            discriminant = - discriminant;
+
+            form x = DeserializeForm(discriminant, x_s, form_size);
+            form y = DeserializeForm(discriminant, y_s, form_size);
+            int d_bits = discriminant.num_bits();
+            std::vector<uint8_t> serialization = SerializeForm(x, d_bits);
+            VectorAppend(serialization, SerializeForm(y, d_bits));
+            std::vector<uint8_t> result = HashInt(serialization, int_length).to_bytes();
+
+            // Allocate memory for the result and copy data
+            uint8_t* resultData = new uint8_t[result.size()];
+            std::copy(result.begin(), result.end(), resultData);
+
+            return ByteArray  { resultData, result.size() };
+        } catch (...) {
+            record_exception();
+            return ByteArray { nullptr, 0 };
+        }
+    }
 }
@@ -9,6 +9,17 @@
 extern "C" {
 #endif
 
+// Kinds of failure reported by chiavdf_last_error_kind
+#define CHIAVDF_ERROR_NONE 0
+#define CHIAVDF_ERROR_EXCEPTION 1
+#define CHIAVDF_ERROR_BAD_ALLOC 2
+#define CHIAVDF_ERROR_UNKNOWN 3
+#define CHIAVDF_ERROR_CANCELLED 4
+
+// Kind and message of the last exception caught by a wrapper on the calling thread
+int chiavdf_last_error_kind();
+const char* chiavdf_last_error_message();
+
 bool create_discriminant_wrapper(const uint8_t* seed, size_t seed_size, size_t size_bits, uint8_t* result);
 
 // Define a struct to hold the byte array and its length
@@ -16,15 +27,79 @@ typedef struct {
     uint8_t* data;
     size_t length;
 } ByteArray;
-ByteArray prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t x_s_size, uint64_t num_iterations);
-ByteArray evaluate_to_prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t x_s_size, uint64_t num_iterations);
-ByteArray prove_only_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, uint64_t num_iterations);
+// Called with `context` and the number of squarings done, returns false to stop the evaluation
+typedef bool (*ProgressCallback)(void* context, uint64_t iterations);
+// Called with `context`, the number of squarings done, the k and l parameters of the evaluation,
+// the current form and the intermediates collected since the previous checkpoint, returns false
+// to stop the evaluation
+typedef bool (*CheckpointCallback)(void* context, uint64_t iterations, int k, int l, const uint8_t* y_s, size_t form_size, const uint8_t* intermediates, size_t intermediates_size);
+
+// Optional hooks into the evaluation loop, all fields may be null or 0.
+// The evaluation stops once `cancelled` is set, and the wrappers fail with CHIAVDF_ERROR_CANCELLED.
+// It must point to an atomic boolean, e.g. a Rust `AtomicBool`.
+// The callbacks are called with `context` every `progress_interval` and `checkpoint_interval`
+// squarings. The wrappers also fail with CHIAVDF_ERROR_CANCELLED if one of them returns false.
+typedef struct {
+    const bool* cancelled;
+    ProgressCallback progress;
+    uint64_t progress_interval;
+    CheckpointCallback checkpoint;
+    uint64_t checkpoint_interval;
+    void* context;
+} EvalHooks;
+
+// `hooks` may be null
+ByteArray prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t x_s_size, uint64_t num_iterations, const EvalHooks* hooks);
+ByteArray evaluate_to_prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t x_s_size, uint64_t num_iterations, const EvalHooks* hooks);
+// Continue `evaluate_to_prove_wrapper` from a checkpoint: `y_s` after `iterations_done` squarings
+// and the intermediates collected until then, with the k and l reported to the checkpoint callback
+ByteArray resume_evaluation_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* y_s, size_t form_size, const uint8_t* inter_s, size_t intermediates_size, uint64_t iterations_done, uint64_t num_iterations, int k, int l, const EvalHooks* hooks);
+// Return x^2^T without intermediates, the checkpoint hooks are ignored
+ByteArray evaluate_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t x_s_size, uint64_t num_iterations, const EvalHooks* hooks);
+// `cancelled` may be null, like the flag of EvalHooks
+ByteArray prove_only_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, uint64_t num_iterations, const bool* cancelled);
 ByteArray prove_int_only_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, const uint8_t* inter_s, size_t intermediate_size, uint64_t num_iterations);
+// Pietrzak proof of y = x^2^T from the intermediates of evaluate_to_prove_wrapper, the forms of mu
+// of each round
+ByteArray pietrzak_prove_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, const uint8_t* inter_s, size_t intermediates_size, uint64_t num_iterations);
+bool pietrzak_verify_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, const uint8_t* proof_s, size_t proof_size, uint64_t num_iterations);
+#ifdef CHIAVDFC_TIMELORD
+// Only in builds linking the fast path of vdf_client, see BUILD_CHIAVDFC_TIMELORD
+ByteArray prove_n_wesolowski_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size, uint64_t num_iterations, uint64_t recursion);
+
+// The N-Wesolowski session of vdf_client, squaring x on the fast path until stopped and proving
+// any number of squarings on request
+typedef struct ProverSession ProverSession;
+// Return null and record the error on failure
+ProverSession* prover_session_start(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size);
+uint64_t prover_session_iterations(const ProverSession* session);
+// Wait until `num_iterations` squarings are done and proven, and return y followed by the proof,
+// with its number of extra segments in `witness_type`
+ByteArray prover_session_prove(ProverSession* session, uint64_t num_iterations, uint8_t* witness_type);
+// Cancel the pending and later proofs, and wait for the threads. Return false and record the error
+// on failure.
+bool prover_session_stop(ProverSession* session);
+// Stop the session, which must not be used anymore
+void prover_session_free(ProverSession* session);
+#endif
+
 bool verify_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, const uint8_t* proof_s, size_t form_size, uint64_t num_iterations);
+// Verify `count` Wesolowski proofs at once, given as x, y and proof forms one after the other in
+// `proofs_s`, with one random weight of `weight_size` big-endian bytes each
+bool verify_batch_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* proofs_s, size_t form_size, const uint64_t* num_iterations, const uint8_t* weights, size_t weight_size, size_t count);
 bool verify_n_wesolowski_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* proof_blob, size_t proof_blob_size, uint64_t num_iterations, uint64_t recursion);
+// Derive the discriminant of `disc_size_bits` bits from the challenge and verify an N-Wesolowski proof
+bool create_discriminant_and_verify_n_wesolowski_wrapper(const uint8_t* challenge, size_t challenge_size, size_t disc_size_bits, const uint8_t* x_s, size_t x_s_size, const uint8_t* proof_blob, size_t proof_blob_size, uint64_t num_iterations, uint64_t recursion);
+// Verify a compact N-Wesolowski proof, in which y is replaced by B = GetB(x', y) and the blob starts
+// with the proof of the last segment. Write y to `y_s` if the proof is valid.
+bool verify_n_wesolowski_with_b_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* b_bytes, size_t b_size, const uint8_t* x_s, size_t x_s_size, const uint8_t* proof_blob, size_t proof_blob_size, uint64_t num_iterations, uint64_t recursion, uint8_t* y_s, size_t y_s_size);
+// Return the B of the compact form of an N-Wesolowski proof, after checking its segments
+ByteArray get_b_from_n_wesolowski_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t x_s_size, const uint8_t* proof_blob, size_t proof_blob_size, uint64_t num_iterations, uint64_t recursion);
 void delete_byte_array(ByteArray array);
 
 ByteArray from_ab(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* a_bytes, size_t a_size, const uint8_t* b_bytes, size_t b_size);
+ByteArray from_ab_reduced(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* a_bytes, size_t a_size, const uint8_t* b_bytes, size_t b_size, bool b_negative);
+ByteArray decompress_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size);
 ByteArray identity_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size);
 ByteArray generator_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size);
 ByteArray power_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, size_t form_size, const uint8_t* power, size_t power_size);
@@ -32,6 +107,10 @@ ByteArray multiply_wrapper(const uint8_t* discriminant_bytes, size_t discriminan
 
 bool hash_int_wrapper(const uint8_t* seed, size_t seed_size, size_t size_bits, uint8_t* result);
 bool hash_prime_wrapper(const uint8_t* seed, size_t seed_size, size_t size_bits, uint8_t* result);
+// The Fiat-Shamir challenge of x and y: the prime B = GetB(D, x, y), or HashInt of their
+// serializations with `int_length` bits, a multiple of 8 of at most 256
+ByteArray challenge_prime_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size);
+ByteArray challenge_int_wrapper(const uint8_t* discriminant_bytes, size_t discriminant_size, const uint8_t* x_s, const uint8_t* y_s, size_t form_size, size_t int_length);
 #ifdef __cplusplus
 }
 #endif
@@ -109,8 +109,13 @@ class OneWesolowskiCallback: public WesolowskiCallback {
 
 class TwoWesolowskiCallback: public WesolowskiCallback {
   public:
-    TwoWesolowskiCallback(integer& D, form f) : WesolowskiCallback(D) {
-        int space_needed = kSwitchIters / 10 + (kMaxItersAllowed - kSwitchIters) / 100;
+    // Room is made for the forms of up to max_iters squarings, plus the squarings repeated_square
+    // does past them before it stops.
+    TwoWesolowskiCallback(integer& D, form f, uint64_t max_iters = kMaxItersAllowed) : WesolowskiCallback(D) {
+        uint64_t iters = std::min<uint64_t>(max_iters + checkpoint_interval + 100, kMaxItersAllowed);
+        // The switch to every 100th form happens at the first checkpoint past kSwitchIters
+        uint64_t space_needed = (iters < kSwitchIters) ? iters / 10 + 1
+            : (kSwitchIters + checkpoint_interval + 100) / 10 + (iters - kSwitchIters) / 100 + 1;
         forms.reset(new form[space_needed]);
         forms[0] = f;
         kl = 10;
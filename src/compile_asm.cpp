@@ -12,6 +12,12 @@ bool enable_all_instructions=false;
 
 #define COMPILE_ASM
 
+// macOS only links position independent code. Elsewhere it is needed when the assembly is linked
+// into a shared library or a PIE executable, e.g. through chiavdfc.
+#ifdef CHIAOSX
+    #define CHIA_ASM_PIC 1
+#endif
+
 #ifdef TEST_ASM
     #undef TEST_ASM
 #endif
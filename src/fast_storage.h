@@ -11,7 +11,8 @@
 
 #include "vdf_new.h"
 
-extern bool new_event;
+extern uint64_t new_events;
+extern bool quiet_mode;
 extern std::mutex new_event_mutex;
 extern std::condition_variable new_event_cv;
 
@@ -39,7 +40,9 @@ class FastStorage {
             storage_threads[i].join();
         }
         delete[] intermediates_stored;
-        std::cout << "Fast storage fully stopped.\n" << std::flush;
+        if (!quiet_mode) {
+            std::cout << "Fast storage fully stopped.\n" << std::flush;
+        }
     }
 
     void AddIntermediates(uint64_t iter) {
@@ -58,7 +61,7 @@ class FastStorage {
         if (has_event) {
             {
                 std::lock_guard<std::mutex> lk(new_event_mutex);
-                new_event = true;
+                new_events++;
             }
             new_event_cv.notify_all();
         }
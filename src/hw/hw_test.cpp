@@ -69,7 +69,8 @@ int hw_test_main(int argc, char **argv)
     for (uint8_t i = 0; i < n_vdfs; i++) {
         struct vdf_state *vdf = &vdfs[i];
 
-        init_vdf_state(vdf, NULL, discrs[i], init_form, n_iters, i);
+        init_vdf_state(vdf, NULL, discrs[i], init_form,
+                bqfc_get_compr_size(integer(discrs[i]).num_bits()), n_iters, i);
 
         //run_hw(vdf->d, n_iters, vdf);
         //vdf_threads[i] = std::thread(start_vdf_job, vdf, i);
@@ -7,6 +7,7 @@
 #include "pll_freqs.hpp"
 
 #include <arpa/inet.h>
+#include <cstddef>
 #include <cstdio>
 #include <fcntl.h>
 #include <getopt.h>
@@ -53,6 +54,7 @@ struct vdf_client {
     ChiaDriver *drv;
 };
 
+// Only the first bqfc_get_compr_size(d_bits) bytes of the proof are sent
 struct vdf_proof_segm {
     uint8_t iters[sizeof(uint64_t)];
     uint8_t B[HW_VDF_B_SIZE];
@@ -239,6 +241,9 @@ void handle_proofs(struct vdf_client *client, struct vdf_conn *conn)
 {
     struct vdf_proof *proof;
     int i;
+    size_t form_size = bqfc_get_compr_size(mpz_sizeinbase(conn->vdf.D.impl, 2));
+    size_t data_size = 8 + 8 + 1 + form_size * 2;
+    size_t segm_size = offsetof(struct vdf_proof_segm, proof) + form_size;
     while ((i = hw_retrieve_proof(&conn->vdf, &proof)) >= 0) {
         uint8_t data[8 + 8 + 1 + BQFC_FORM_SIZE * 2];
         char tl_data[sizeof(data) * 2 + 5] = {0};
@@ -246,14 +251,14 @@ void handle_proofs(struct vdf_client *client, struct vdf_conn *conn)
         LOG_INFO("VDF %d: Proof retrieved for iters=%lu", conn->vdf.idx, proof->iters);
 
         Int64ToBytes(&data[0], proof->iters);
-        Int64ToBytes(&data[8], BQFC_FORM_SIZE);
-        memcpy(&data[16], proof->y, BQFC_FORM_SIZE);
-        data[16 + BQFC_FORM_SIZE] = i;
-        memcpy(&data[17 + BQFC_FORM_SIZE], proof->proof, BQFC_FORM_SIZE);
-
-        tl_enc_hex(&tl_data[4], data, sizeof(data));
-        Int32ToBytes((uint8_t *)tl_data, (sizeof(data) + i * sizeof(vdf_proof_segm)) * 2);
-        write_data(conn, tl_data, sizeof(tl_data) - 1);
+        Int64ToBytes(&data[8], form_size);
+        memcpy(&data[16], proof->y, form_size);
+        data[16 + form_size] = i;
+        memcpy(&data[17 + form_size], proof->proof, form_size);
+
+        tl_enc_hex(&tl_data[4], data, data_size);
+        Int32ToBytes((uint8_t *)tl_data, (data_size + i * segm_size) * 2);
+        write_data(conn, tl_data, data_size * 2 + 4);
         while (i) {
             struct vdf_proof_segm *segm = (struct vdf_proof_segm *)data;
 
@@ -262,10 +267,10 @@ void handle_proofs(struct vdf_client *client, struct vdf_conn *conn)
             proof = &conn->vdf.proofs[proof->prev];
             Int64ToBytes(segm->iters, proof->seg_iters);
             memcpy(segm->B, proof->B, sizeof(proof->B));
-            memcpy(segm->proof, proof->proof, sizeof(proof->proof));
+            memcpy(segm->proof, proof->proof, form_size);
 
-            tl_enc_hex(tl_data, data, sizeof(*segm));
-            write_data(conn, tl_data, sizeof(*segm) * 2);
+            tl_enc_hex(tl_data, data, segm_size);
+            write_data(conn, tl_data, segm_size * 2);
         }
     }
 }
@@ -310,7 +315,7 @@ void handle_conn(struct vdf_client *client, struct vdf_conn *conn)
         }
 
         init_form = (uint8_t *)&buf[4 + d_size + 1];
-        init_vdf_state(vdf, &client->opts.vpo, d_str, init_form, n_iters, vdf->idx);
+        init_vdf_state(vdf, &client->opts.vpo, d_str, init_form, (uint8_t)buf[4 + d_size], n_iters, vdf->idx);
         start_hw_vdf(client->drv, vdf->D.impl, vdf->last_val.a, vdf->last_val.b,
                 vdf->target_iters, vdf->idx);
         write_data(conn, "OK", 2);
@@ -0,0 +1,134 @@
+#ifndef PIETRZAK_H
+#define PIETRZAK_H
+
+#include "verifier.h"
+#include "prover_slow.h"
+#include <functional>
+
+// Pietrzak proofs of y = x^(2^T). Each round halves T: the prover sends mu = x^(2^(T/2)), and both
+// claims x^(2^(T/2)) = mu and mu^(2^(T/2)) = y are folded into x' = x^r * mu, y' = mu^r * y with a
+// random r, until T is 1. An odd T is first made even by squaring y. The proof is the list of mu.
+
+const int kPietrzakChallengeBits = 128;
+
+integer GetPietrzakChallenge(integer& D, form& x, form& y, form& mu, uint64_t num_iterations) {
+    int d_bits = D.num_bits();
+    std::vector<uint8_t> seed = SerializeForm(x, d_bits);
+    VectorAppend(seed, SerializeForm(y, d_bits));
+    VectorAppend(seed, SerializeForm(mu, d_bits));
+    for (int i = 7; i >= 0; i--) {
+        seed.push_back(num_iterations >> (8 * i));
+    }
+    return HashInt(seed, kPietrzakChallengeBits);
+}
+
+uint64_t GetPietrzakRounds(uint64_t num_iterations) {
+    uint64_t rounds = 0;
+    for (uint64_t t = num_iterations; t > 1; t = (t + 1) / 2) {
+        rounds++;
+    }
+    return rounds;
+}
+
+void SquarePietrzak(form& f, uint64_t times, integer& D, integer& L, PulmarkReducer& reducer) {
+    for (uint64_t i = 0; i < times; i++) {
+        nudupl_form(f, f, D, L);
+        reducer.reduce(f);
+    }
+}
+
+// Fold the claim x^(2^t) = y with mu, returning the number of iterations of the next claim
+uint64_t FoldPietrzak(integer& D, integer& L, PulmarkReducer& reducer, form& x, form& y, form& mu, uint64_t t, integer& r) {
+    if (t % 2 == 1) {
+        SquarePietrzak(y, 1, D, L, reducer);
+        t++;
+    }
+    r = GetPietrzakChallenge(D, x, y, mu, t);
+    x = FastPowFormNucomp(x, D, r, L, reducer);
+    nucomp_form(x, x, mu, D, L);
+    reducer.reduce(x);
+    form mu_r = FastPowFormNucomp(mu, D, r, L, reducer);
+    nucomp_form(y, y, mu_r, D, L);
+    reducer.reduce(y);
+    return t / 2;
+}
+
+// The intermediates are x^(2^(i * kl)) as kept by ProveSlow and EvalSlow, of which only a few are
+// needed, so they are read through `intermediate` on demand. While few rounds are done, each mu is
+// a product of a few of the forms x^(2^m), which are found from the nearest intermediate. Once that
+// gets more expensive than squaring, mu is computed from x by squaring, which costs T/2^(i+1)
+// squarings in round i.
+std::vector<uint8_t> ProvePietrzak(integer& D, form& x, form& y, std::function<form(size_t)> const& intermediate,
+                                   size_t intermediates_count, uint64_t num_iterations) {
+    int k, l;
+    GetSlowParameters(num_iterations, k, l);
+    uint64_t const kl = k * l;
+    if (intermediates_count != (num_iterations + kl - 1) / kl) {
+        throw std::runtime_error("The number of intermediates doesn't match the number of iterations");
+    }
+
+    integer L = root(-D, 4);
+    PulmarkReducer reducer;
+    // The form x^(2^m), where m may exceed T by the squarings of an odd T
+    auto chain = [&](uint64_t m) {
+        form f = m >= num_iterations ? y : intermediate(m / kl);
+        SquarePietrzak(f, m >= num_iterations ? m - num_iterations : m % kl, D, L, reducer);
+        return f;
+    };
+
+    // The current x is the product of x^(2^offset * exponent) over these terms, while they are used
+    std::vector<std::pair<uint64_t, integer>> terms = {{0, integer(1)}};
+    bool use_terms = true;
+    form x_i = x, y_i = y;
+    int d_bits = D.num_bits();
+    std::vector<uint8_t> result;
+    for (uint64_t t = num_iterations; t > 1;) {
+        uint64_t const half = (t + 1) / 2;
+        form mu;
+        // The first term has the largest exponent, the product of all the challenges so far
+        uint64_t exponent_bits = terms.front().second.num_bits();
+        use_terms = use_terms && terms.size() * (kl + exponent_bits / 2) < half;
+        if (use_terms) {
+            std::vector<form> bases;
+            std::vector<integer> exponents;
+            for (auto const& term : terms) {
+                bases.push_back(chain(term.first + half));
+                exponents.push_back(term.second);
+            }
+            mu = MultiPowFormNucomp(bases, exponents, D, L, reducer);
+        } else {
+            mu = x_i;
+            SquarePietrzak(mu, half, D, L, reducer);
+        }
+        VectorAppend(result, SerializeForm(mu, d_bits));
+
+        integer r;
+        t = FoldPietrzak(D, L, reducer, x_i, y_i, mu, t, r);
+        if (use_terms) {
+            std::vector<std::pair<uint64_t, integer>> next;
+            for (auto const& term : terms) {
+                next.push_back({term.first, term.second * r});
+                next.push_back({term.first + half, term.second});
+            }
+            terms = std::move(next);
+        }
+    }
+    return result;
+}
+
+bool VerifyPietrzak(integer& D, form x, form y, std::vector<form> const& mus, uint64_t num_iterations) {
+    if (mus.size() != GetPietrzakRounds(num_iterations)) {
+        return false;
+    }
+    integer L = root(-D, 4);
+    PulmarkReducer reducer;
+    uint64_t t = num_iterations;
+    for (form mu : mus) {
+        integer r;
+        t = FoldPietrzak(D, L, reducer, x, y, mu, t, r);
+    }
+    SquarePietrzak(x, t, D, L, reducer);
+    return x == y;
+}
+
+#endif // PIETRZAK_H
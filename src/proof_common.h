@@ -2,6 +2,7 @@
 #define PROOF_COMMON_H
 #include "Reducer.h"
 #include "bqfc.c"
+#include <atomic>
 
 const int B_bits = 264;
 const int B_bytes = (B_bits + 7) / 8;
@@ -62,7 +63,7 @@ integer HashPrime(std::vector<uint8_t> seed, int length, vector<int> bitmask) {
 std::vector<unsigned char> SerializeForm(form &y, int d_bits)
 {
     y.reduce();
-    std::vector<unsigned char> res(BQFC_FORM_SIZE);
+    std::vector<unsigned char> res(bqfc_get_compr_size(d_bits));
     bqfc_serialize(res.data(), y.a.impl, y.b.impl, d_bits);
     return res;
 }
@@ -125,7 +126,10 @@ class PulmarkReducer {
     }
 };
 
-form FastPowFormNucomp(form x, integer &D, integer num_iterations, integer &L, PulmarkReducer& reducer)
+// Stops early once `cancelled` is set, if given, in which case the result is meaningless and callers
+// must check the flag
+form FastPowFormNucomp(form x, integer &D, integer num_iterations, integer &L, PulmarkReducer& reducer,
+                       const std::atomic<bool>* cancelled = nullptr)
 {
     if (!mpz_sgn(num_iterations.impl))
         return form::identity(D);
@@ -135,6 +139,9 @@ form FastPowFormNucomp(form x, integer &D, integer num_iterations, integer &L, P
 
     // Do exponentiation by squaring from top bits of exponent to bottom
     for (i = num_iterations.num_bits() - 2; i >= 0; i--) {
+        if (cancelled != nullptr && cancelled->load(std::memory_order_relaxed)) {
+            break;
+        }
         nudupl_form(res, res, D, L);
         if (res.a.impl->_mp_size > max_size) {
             // Reduce only when 'a' exceeds a half of the discriminant size
@@ -150,4 +157,34 @@ form FastPowFormNucomp(form x, integer &D, integer num_iterations, integer &L, P
     return res;
 }
 
+// Compute the product of bases[i]^exponents[i], sharing the squarings between all the bases
+form MultiPowFormNucomp(const std::vector<form>& bases, const std::vector<integer>& exponents, integer &D, integer &L, PulmarkReducer& reducer)
+{
+    int max_bits = 0;
+    for (const integer& exponent : exponents) {
+        max_bits = std::max(max_bits, exponent.num_bits());
+    }
+
+    form res = form::identity(D);
+    int max_size = -D.impl->_mp_size / 2;
+    for (int i = max_bits - 1; i >= 0; i--) {
+        nudupl_form(res, res, D, L);
+        if (res.a.impl->_mp_size > max_size) {
+            reducer.reduce(res);
+        }
+
+        for (size_t j = 0; j < bases.size(); j++) {
+            if (mpz_tstbit(exponents[j].impl, i)) {
+                nucomp_form(res, res, bases[j], D, L);
+                if (res.a.impl->_mp_size > max_size) {
+                    reducer.reduce(res);
+                }
+            }
+        }
+    }
+
+    reducer.reduce(res);
+    return res;
+}
+
 # endif // PROOF_COMMON_H
@@ -0,0 +1,79 @@
+#ifndef PROVER_SESSION_H
+#define PROVER_SESSION_H
+
+#include "vdf.h"
+
+// The N-Wesolowski session of vdf_client without its socket: squares x with repeated_square until
+// stopped, while the ProverManager proves the segments of FastAlgorithmCallback, and proves any
+// number of squarings on request.
+class FastProverSession {
+  public:
+    // The parameters of vdf_client
+    static const int kSegments = 8;
+    static const int kThreadCount = 3;
+
+    FastProverSession(integer& D, form& x) : D(D), L(root(-D, 4)) {
+        const bool multi_proc_machine = (std::thread::hardware_concurrency() >= 16) ? true : false;
+        weso = std::make_unique<FastAlgorithmCallback>(kSegments, this->D, x, multi_proc_machine);
+        if (multi_proc_machine) {
+            fast_storage = std::make_unique<FastStorage>(weso.get());
+        }
+        pm = std::make_unique<ProverManager>(this->D, weso.get(), fast_storage.get(), kSegments, kThreadCount);
+        pm->start();
+        // Last, as nothing stops the squaring if the constructor throws after it started
+        try {
+            vdf_worker = std::thread(repeated_square, 0, x, std::ref(this->D), std::ref(this->L), weso.get(),
+                fast_storage.get(), std::ref(stopped));
+        } catch (...) {
+            pm->stop();
+            throw;
+        }
+    }
+
+    ~FastProverSession() {
+        Stop();
+    }
+
+    // Stop squaring and proving, and wait for the threads. The pending proofs and the later ones are
+    // cancelled.
+    void Stop() {
+        std::lock_guard<std::mutex> lock(stop_mutex);
+        if (stopped) {
+            return;
+        }
+        stopped = true;
+        pm->stop();
+        vdf_worker.join();
+    }
+
+    uint64_t Iterations() const {
+        return weso->iterations;
+    }
+
+    // Wait until num_iterations squarings are done and proven, and return y followed by the
+    // N-Wesolowski proof, with its number of extra segments as witness_type. The proof is empty if
+    // the session is stopped before. Like the 2-Wesolowski prover, the timelord does not square
+    // past kMaxItersAllowed - 500000.
+    Proof Prove(uint64_t num_iterations) {
+        if (num_iterations == 0) {
+            throw std::invalid_argument("Cannot prove zero iterations");
+        }
+        if (num_iterations + checkpoint_interval >= kMaxItersAllowed - 500000) {
+            throw std::invalid_argument("Too many iterations for the prover session");
+        }
+        return pm->Prove(num_iterations);
+    }
+
+  private:
+    integer D;
+    integer L;
+    // The storage and the provers use the callback, so they go after it
+    std::unique_ptr<FastAlgorithmCallback> weso;
+    std::unique_ptr<FastStorage> fast_storage;
+    std::atomic<bool> stopped{false};
+    std::thread vdf_worker;
+    std::unique_ptr<ProverManager> pm;
+    std::mutex stop_mutex;
+};
+
+#endif // PROVER_SESSION_H
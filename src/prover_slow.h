@@ -1,3 +1,6 @@
+#ifndef PROVER_SLOW_H
+#define PROVER_SLOW_H
+
 #include "include.h"
 #include "create_discriminant.h"
 #include "integer_common.h"
@@ -5,11 +8,39 @@
 #include "nucomp.h"
 #include "picosha2.h"
 #include "proof_common.h"
+#include "util.h"
 #include <sys/stat.h>
+#include <atomic>
+#include <functional>
 
 
 // TODO: Refactor to use 'Prover' class once new_vdf is merged in.
 
+// Optional hooks into the squaring loop of ProveSlow and EvalSlow
+struct SlowEvalHooks {
+    // Stop the evaluation once set
+    const std::atomic<bool>* cancelled = nullptr;
+    // Called with the number of squarings done every progress_interval squarings, the evaluation
+    // stops if it returns false
+    std::function<bool(uint64_t)> progress;
+    uint64_t progress_interval = 0;
+    // Called with the number of squarings done, the current form and the intermediates collected
+    // so far every checkpoint_interval squarings, the evaluation stops if it returns false
+    std::function<bool(uint64_t, form&, const std::vector<form>&)> checkpoint;
+    uint64_t checkpoint_interval = 0;
+
+    // Whether the evaluation should stop after `done` squarings
+    bool stop(uint64_t done, form& y, const std::vector<form>& intermediates) const {
+        if (cancelled != nullptr && cancelled->load(std::memory_order_relaxed)) {
+            return true;
+        }
+        if (progress && progress_interval != 0 && done % progress_interval == 0 && !progress(done)) {
+            return true;
+        }
+        return checkpoint && checkpoint_interval != 0 && done % checkpoint_interval == 0 && !checkpoint(done, y, intermediates);
+    }
+};
+
 void ApproximateParameters(uint64_t T, int& l, int& k) {
     double log_memory = 23.25349666;
     double log_T = log2(T);
@@ -21,6 +52,13 @@ void ApproximateParameters(uint64_t T, int& l, int& k) {
     k = std::max(std::round(log(intermediate) - log(log(intermediate)) + 0.25), 1.0);
 }
 
+// The k and l parameters of ProveSlow and EvalSlow, the intermediates are kept every k * l squarings
+void GetSlowParameters(uint64_t num_iterations, int& k, int& l) {
+    ApproximateParameters(num_iterations, l, k);
+    if (k <= 0) k = 1;
+    if (l <= 0) l = 1;
+}
+
 uint64_t GetBlock(uint64_t i, uint64_t k, uint64_t T, integer& B) {
     integer res = FastPow(2, T - k * (i + 1), B);
     mpz_mul_2exp(res.impl, res.impl, k);
@@ -94,29 +132,22 @@ std::vector<uint8_t> EvaluateOnly(integer& D, form& x, uint64_t num_iterations,
     return result;
 }
 
-std::vector<uint8_t> ProveSlow(integer& D, form& x, uint64_t num_iterations, std::string shutdown_file_path) {
-    integer L = root(-D, 4);
-    PulmarkReducer reducer;
-    form y = form::from_abd(x.a, x.b, D);
-    int d_bits = D.num_bits();
-
-    int k, l;
-    ApproximateParameters(num_iterations, l, k);
-    if (k <= 0) k = 1;
-    if (l <= 0) l = 1;
-    int const kl = k * l;
-
-    uint64_t const size_vec = (num_iterations + kl - 1) / kl;
-    std::vector<form> intermediates(size_vec);
-    form* cursor = intermediates.data();
-    for (uint64_t i = 0; i < num_iterations; i++) {
-        if (i % kl == 0) {
-            *cursor = y;
-            ++cursor;
+// Continue the squarings of ProveSlow and EvalSlow from `y`, the form after `done` squarings,
+// storing every kl-th form in `intermediates`, or none if kl is 0. Returns false if the evaluation
+// was stopped.
+bool SquareSlow(integer& D, integer& L, PulmarkReducer& reducer, form& y, uint64_t done, uint64_t num_iterations, int kl,
+                std::vector<form>& intermediates, const std::string& shutdown_file_path, const SlowEvalHooks& hooks) {
+    for (uint64_t i = done; i < num_iterations; i++) {
+        if (kl != 0 && i % kl == 0) {
+            intermediates.push_back(y);
         }
         nudupl_form(y, y, D, L);
         reducer.reduce(y);
 
+        if (hooks.stop(i + 1, y, intermediates)) {
+            return false;
+        }
+
         // Check for cancellation every 65535 interations
         if ((i&0xffff)==0) {
             // Only if we have a shutdown path
@@ -126,11 +157,44 @@ std::vector<uint8_t> ProveSlow(integer& D, form& x, uint64_t num_iterations, std
                 int statrst = stat(shutdown_file_path.c_str(), &buffer);
                 if ((statrst != 0) && (errno != EINTR)) {
                     // shutdown file doesn't exist, abort out
-                    return {};
+                    return false;
                 }
             }
         }
     }
+    return true;
+}
+
+// Square x num_iterations times like EvalSlow, without keeping intermediates (EvaluateOnly raises x
+// to the power num_iterations instead). Returns an empty vector if the evaluation was stopped.
+std::vector<uint8_t> EvalOnlySlow(integer& D, form& x, uint64_t num_iterations, std::string shutdown_file_path, const SlowEvalHooks& hooks = SlowEvalHooks()) {
+    integer L = root(-D, 4);
+    PulmarkReducer reducer;
+    form y = form::from_abd(x.a, x.b, D);
+    int d_bits = D.num_bits();
+
+    std::vector<form> intermediates;
+    if (!SquareSlow(D, L, reducer, y, 0, num_iterations, 0, intermediates, shutdown_file_path, hooks)) {
+        return {};
+    }
+    return SerializeForm(y, d_bits);
+}
+
+std::vector<uint8_t> ProveSlow(integer& D, form& x, uint64_t num_iterations, std::string shutdown_file_path, const SlowEvalHooks& hooks = SlowEvalHooks()) {
+    integer L = root(-D, 4);
+    PulmarkReducer reducer;
+    form y = form::from_abd(x.a, x.b, D);
+    int d_bits = D.num_bits();
+
+    int k, l;
+    GetSlowParameters(num_iterations, k, l);
+    int const kl = k * l;
+
+    std::vector<form> intermediates;
+    intermediates.reserve((num_iterations + kl - 1) / kl);
+    if (!SquareSlow(D, L, reducer, y, 0, num_iterations, kl, intermediates, shutdown_file_path, hooks)) {
+        return {};
+    }
 
     form proof = GenerateWesolowski(y, x, D, reducer, intermediates, num_iterations, k, l);
     std::vector<uint8_t> result = SerializeForm(y, d_bits);
@@ -139,63 +203,49 @@ std::vector<uint8_t> ProveSlow(integer& D, form& x, uint64_t num_iterations, std
     return result;
 }
 
-std::vector<uint8_t> EvalSlow(integer& D, form& x, uint64_t num_iterations, std::string shutdown_file_path) {
+// Continue EvalSlow from `y`, the form after `done` squarings, with the intermediates collected
+// until then, e.g. from a checkpoint
+std::vector<uint8_t> ResumeEvalSlow(integer& D, form& y, uint64_t done, uint64_t num_iterations, std::vector<form> intermediates,
+                                    std::string shutdown_file_path, const SlowEvalHooks& hooks = SlowEvalHooks()) {
     integer L = root(-D, 4);
     PulmarkReducer reducer;
-    form y = form::from_abd(x.a, x.b, D);
     int d_bits = D.num_bits();
 
     int k, l;
-    ApproximateParameters(num_iterations, l, k);
-    if (k <= 0) k = 1;
-    if (l <= 0) l = 1;
+    GetSlowParameters(num_iterations, k, l);
     int const kl = k * l;
 
-    uint64_t const size_vec = (num_iterations + kl - 1) / kl;
-    std::vector<form> intermediates(size_vec);
-    form* cursor = intermediates.data();
-    for (uint64_t i = 0; i < num_iterations; i++) {
-        if (i % kl == 0) {
-            *cursor = y;
-            ++cursor;
-        }
-        nudupl_form(y, y, D, L);
-        reducer.reduce(y);
-
-        // Check for cancellation every 65535 interations
-        if ((i&0xffff)==0) {
-            // Only if we have a shutdown path
-            if (shutdown_file_path!="") {
-                struct stat buffer;
-            
-                int statrst = stat(shutdown_file_path.c_str(), &buffer);
-                if ((statrst != 0) && (errno != EINTR)) {
-                    // shutdown file doesn't exist, abort out
-                    return {};
-                }
-            }
-        }
+    if (done > num_iterations || intermediates.size() != (done + kl - 1) / kl) {
+        throw std::invalid_argument("Checkpoint does not match the number of iterations");
+    }
+    intermediates.reserve((num_iterations + kl - 1) / kl);
+    if (!SquareSlow(D, L, reducer, y, done, num_iterations, kl, intermediates, shutdown_file_path, hooks)) {
+        return {};
     }
 
     std::vector<uint8_t> result = SerializeForm(y, d_bits);
     for (form inter : intermediates){
         std::vector<uint8_t> inter_ser = SerializeForm(inter, d_bits);
-        std::string inter_s(inter_ser.begin(), inter_ser.end());
         result.insert(result.end(), inter_ser.begin(), inter_ser.end());
         }
     return result;
 }
 
+std::vector<uint8_t> EvalSlow(integer& D, form& x, uint64_t num_iterations, std::string shutdown_file_path, const SlowEvalHooks& hooks = SlowEvalHooks()) {
+    form y = form::from_abd(x.a, x.b, D);
+    return ResumeEvalSlow(D, y, 0, num_iterations, {}, shutdown_file_path, hooks);
+}
+
 std::vector<uint8_t> ProveInter(integer& D, form& x, form& y, std::vector<form> const& intermediates, uint64_t num_iterations) {
     PulmarkReducer reducer;
     int d_bits = D.num_bits();
 
     int k, l;
-    ApproximateParameters(num_iterations, l, k);
-    if (k <= 0) k = 1;
-    if (l <= 0) l = 1;
+    GetSlowParameters(num_iterations, k, l);
 
     form proof = GenerateWesolowski(y, x, D, reducer, intermediates, num_iterations, k, l);
     std::vector<uint8_t> result = SerializeForm(proof, d_bits);
     return result;
-}
\ No newline at end of file
+}
+
+#endif // PROVER_SLOW_H
@@ -79,7 +79,7 @@ class TwoWesolowskiProver : public Prover{
     uint64_t done_iterations;
 };
 
-extern bool new_event;
+extern uint64_t new_events;
 extern std::mutex new_event_mutex;
 extern std::condition_variable new_event_cv;
 
@@ -169,9 +169,9 @@ class InterruptableProver: public Prover {
             // Notify event loop a proving thread is free.
             {
                 std::lock_guard<std::mutex> lk(new_event_mutex);
-                new_event = true;
+                new_events++;
             }
-            new_event_cv.notify_one();
+            new_event_cv.notify_all();
             is_fully_finished = true;
         }
     }
@@ -50,15 +50,12 @@
 #include "util.h"
 #include "callback.h"
 #include "fast_storage.h"
-#include <boost/asio.hpp>
 
 #include <atomic>
 #include <optional>
 
 bool warn_on_corruption_in_production=false;
 
-using boost::asio::ip::tcp;
-
 struct akashnil_form {
     // y = ax^2 + bxy + y^2
     mpz_t a;
@@ -70,14 +67,15 @@ struct akashnil_form {
 const int64_t THRESH = 1UL<<31;
 const int64_t EXP_THRESH = 31;
 
-// Notifies ProverManager class each time there's a new event.
-bool new_event = false;
+// Counts the events, each ProverManager event loop wakes up when it changed since it last looked.
+// A counter rather than a flag, so that the loops of several managers don't consume each other's events.
+uint64_t new_events = 0;
 std::condition_variable new_event_cv;
 std::mutex new_event_mutex, cout_lock;
 
 bool debug_mode = false;
-bool fast_algorithm = false;
-bool two_weso = false;
+// Silences the status lines, for the programs embedding the fast path rather than reporting on it
+bool quiet_mode = false;
 
 //always works
 void repeated_square_original(vdf_original &vdfo, form& f, const integer& D, const integer& L, uint64 base, uint64 iterations, INUDUPLListener *nuduplListener) {
@@ -187,22 +185,21 @@ void repeated_square(uint64_t iterations, form f, const integer& D, const intege
             weso->iterations = num_iterations;
 
             // n-weso specific logic.
-            if (fast_algorithm) {
+            if (FastAlgorithmCallback* fweso = dynamic_cast<FastAlgorithmCallback*>(weso)) {
                 if (fast_storage != NULL) {
-                    fast_storage->SubmitCheckpoint(static_cast<FastAlgorithmCallback*> (weso)->y_ret, last_checkpoint);
+                    fast_storage->SubmitCheckpoint(fweso->y_ret, last_checkpoint);
                 } else if (last_checkpoint % (1 << 16) == 0) {
                     // Notify prover event loop, we have a new segment with intermediates stored.
                     {
                         std::lock_guard<std::mutex> lk(new_event_mutex);
-                        new_event = true;
+                        new_events++;
                     }
                     new_event_cv.notify_all();
                 }
             }
 
             // 2-weso specific logic.
-            if (two_weso) {
-                TwoWesolowskiCallback* nweso = (TwoWesolowskiCallback*) weso;
+            if (TwoWesolowskiCallback* nweso = dynamic_cast<TwoWesolowskiCallback*>(weso)) {
                 if (num_iterations >= kSwitchIters && !nweso->LargeConstants()) {
                     uint64 round_up = (100 - num_iterations % 100) % 100;
                     if (round_up > 0) {
@@ -213,7 +210,9 @@ void repeated_square(uint64_t iterations, form f, const integer& D, const intege
                     weso->iterations = num_iterations;
                 }
                 if (num_iterations >= kMaxItersAllowed - 500000) {
-                    std::cout << "Maximum possible number of iterations reached!\n";
+                    if (!quiet_mode) {
+                        std::cout << "Maximum possible number of iterations reached!\n";
+                    }
                     return ;
                 }
             }
@@ -236,7 +235,7 @@ void repeated_square(uint64_t iterations, form f, const integer& D, const intege
             }
         #endif
     }
-    {
+    if (!quiet_mode) {
         // this shouldn't be needed but avoids some false positive in TSAN
         std::lock_guard<std::mutex> lk(cout_lock);
         std::cout << "VDF loop finished. Total iters: " << num_iterations << "\n" << std::flush;
@@ -275,7 +274,7 @@ Proof ProveOneWesolowski(uint64_t iters, integer& D, form f, OneWesolowskiCallba
     proof_serialized = SerializeForm(proof_form, d_bits);
     Proof proof(y_serialized, proof_serialized);
     proof.witness_type = 0;
-    {
+    if (!quiet_mode) {
         // this shouldn't be needed but avoids some false positive in TSAN
         std::lock_guard<std::mutex> lk(cout_lock);
         std::cout << "Got simple weso proof: " << proof.hex() << "\n";
@@ -361,8 +360,10 @@ Proof ProveTwoWeso(integer& D, form x, uint64_t iters, uint64_t done_iterations,
     final_proof.proof = proof_bytes;
     if (depth == 0) {
         final_proof.witness_type = 2;
-        std::cout << "Got 2-wesolowski proof for iteration: " << iters << ".\n";
-        std::cout << "Proof: " << final_proof.hex() << "\n";
+        if (!quiet_mode) {
+            std::cout << "Got 2-wesolowski proof for iteration: " << iters << ".\n";
+            std::cout << "Proof: " << final_proof.hex() << "\n";
+        }
     }
     return final_proof;
 }
@@ -391,16 +392,28 @@ class ProverManager {
         {
             std::lock_guard<std::mutex> lk(new_event_mutex);
             stopped = true;
-            new_event = true;
+            new_events++;
         }
         new_event_cv.notify_all();
         main_loop->join();
-        std::cout << "Prover event loop finished.\n" << std::flush;
+        if (!quiet_mode) {
+            std::cout << "Prover event loop finished.\n" << std::flush;
+        }
 
         for (int i = 0; i < provers.size(); i++) {
             provers[i].first->stop();
         }
-        std::cout << "Segment provers finished.\n" << std::flush;
+        if (!quiet_mode) {
+            std::cout << "Segment provers finished.\n" << std::flush;
+        }
+
+        // Taking the mutexes orders the notifications after the checks of stopped by the waiters
+        {
+            std::lock_guard<std::mutex> lk(proof_mutex);
+        }
+        {
+            std::lock_guard<std::mutex> lk(last_segment_mutex);
+        }
 
         proof_cv.notify_all();
         last_segment_cv.notify_all();
@@ -486,18 +499,24 @@ class ProverManager {
             pending_iters.erase(iteration);
             lk.unlock();
             if (blobs > 63 || proved_iters < iteration - iteration % (1 << 16)) {
-                std::cout << "Warning: Insufficient segments yet. Retrying in 1 minute\n";
+                if (!quiet_mode) {
+                    std::cout << "Warning: Insufficient segments yet. Retrying in 1 minute\n";
+                }
                 proof_segments.clear();
                 proved_iters = 0;
-                this_thread::sleep_for(60s);
                 {
-                    std::lock_guard<std::mutex> lkg(proof_mutex);
+                    // Wait for the minute unless stopped before
+                    std::unique_lock<std::mutex> lkg(proof_mutex);
+                    proof_cv.wait_for(lkg, 60s, [this] { return stopped.load(); });
                     pending_iters.insert(iteration);
                 }
             } else {
                 valid_proof = true;
             }
         }
+        if (!valid_proof) {
+            return Proof();
+        }
         if (!last_segment.is_empty) {
             proof_segments.emplace_back(last_segment);
         }
@@ -518,13 +537,15 @@ class ProverManager {
         }
         Proof proof(y_serialized, proof_serialized);
         proof.witness_type = proof_segments.size() - 1;
-        uint64_t vdf_iteration = weso->iterations;
-        std::cout << "Got proof for iteration: " << iteration << ". ("
-                  << proof_segments.size() - 1 << "-wesolowski proof)\n";
-        std::cout << "Proof: " << proof.hex() << "\n";
-        std::cout << "Current weso iteration: " << vdf_iteration
-                  << ". Extra proof time (in VDF iterations): " << vdf_iteration - iteration
-                  << "\n";
+        if (!quiet_mode) {
+            uint64_t vdf_iteration = weso->iterations;
+            std::cout << "Got proof for iteration: " << iteration << ". ("
+                      << proof_segments.size() - 1 << "-wesolowski proof)\n";
+            std::cout << "Proof: " << proof.hex() << "\n";
+            std::cout << "Current weso iteration: " << vdf_iteration
+                      << ". Extra proof time (in VDF iterations): " << vdf_iteration - iteration
+                      << "\n";
+        }
         return proof;
     }
 
@@ -537,12 +558,13 @@ class ProverManager {
         const bool multi_proc_machine = (std::thread::hardware_concurrency() >= 16) ? true : false;
         bool warned = false;
         bool increased_proving = false;
+        uint64_t seen_events = 0;
         while (!stopped) {
             // Wait for some event to happen.
             {
                 std::unique_lock<std::mutex> lk(new_event_mutex);
-                new_event_cv.wait(lk, []{return new_event;});
-                new_event = false;
+                new_event_cv.wait(lk, [&]{return new_events != seen_events;});
+                seen_events = new_events;
                 lk.unlock();
             }
             if (stopped)
@@ -552,7 +574,9 @@ class ProverManager {
             // VDF running longer than expected, increase proving threads count.
             if (vdf_iteration >= 5e8) {
                 if (!increased_proving && multi_proc_machine) {
-                    std::cout << "Warning: VDF running longer than (expected) 5 minutes. Adding 2 more proving threads.\n";
+                    if (!quiet_mode) {
+                        std::cout << "Warning: VDF running longer than (expected) 5 minutes. Adding 2 more proving threads.\n";
+                    }
                     max_proving_threads += 2;
                     increased_proving = true;
                 }
@@ -616,7 +640,9 @@ class ProverManager {
                         for (int i = segment_count - 1; i >= 0 && proof_blobs < 63; i--) {
                             uint64_t segment_size = (1LL << (16 + 2 * i));
                             if (max_proving_iteration % segment_size != 0) {
-                                std::cout << "Warning: segments don't have the proper sizes.\n";
+                                if (!quiet_mode) {
+                                    std::cout << "Warning: segments don't have the proper sizes.\n";
+                                }
                             } else {
                                 int position = max_proving_iteration / segment_size;
                                 while (position < done_segments[i].size() && !done_segments[i][position].is_empty) {
@@ -654,7 +680,7 @@ class ProverManager {
                         /*y=*/weso->checkpoints[(last_appended[i] + sg_length) / (1 << 16)]
                     );
                     pending_segments[i].emplace_back(sg);
-                    if (!warned && pending_segments[i].size() >= kWindowSize - 2) {
+                    if (!warned && pending_segments[i].size() >= kWindowSize - 2 && !quiet_mode) {
                         warned = true;
                         std::cout << "Warning: VDF loop way ahead of proving loop. "
                                   << "Possible proof corruption. Please increase kWindowSize.\n";
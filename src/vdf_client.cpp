@@ -22,6 +22,7 @@ char disc_size[5];
 int disc_int_size;
 
 uint8_t initial_form_s[BQFC_FORM_SIZE];
+uint8_t initial_form_size;
 
 void WriteProof(uint64_t iteration, Proof& result, tcp::socket& sock) {
     // Writes the number of iterations
@@ -89,9 +90,8 @@ void InitSession(tcp::socket& sock) {
     disc_int_size = atoi(disc_size);
     boost::asio::read(sock, boost::asio::buffer(disc, disc_int_size), error);
 
-    char form_size;
-    boost::asio::read(sock, boost::asio::buffer(&form_size, 1), error);
-    boost::asio::read(sock, boost::asio::buffer(initial_form_s, form_size), error);
+    boost::asio::read(sock, boost::asio::buffer(&initial_form_size, 1), error);
+    boost::asio::read(sock, boost::asio::buffer(initial_form_s, initial_form_size), error);
 
     if (error == boost::asio::error::eof)
         return ; // Connection closed cleanly by peer.
@@ -150,7 +150,7 @@ void SessionFastAlgorithm(tcp::socket& sock) {
         integer D(disc);
         integer L = root(-D, 4);
         PrintInfo("Discriminant = " + to_string(D.impl));
-        form f = DeserializeForm(D, initial_form_s, sizeof(initial_form_s));
+        form f = DeserializeForm(D, initial_form_s, initial_form_size);
         PrintInfo("Initial form: " + to_string(f.a.impl) + " " + to_string(f.b.impl));
         std::vector<std::thread> threads;
         const bool multi_proc_machine = (std::thread::hardware_concurrency() >= 16) ? true : false;
@@ -198,7 +198,7 @@ void SessionOneWeso(tcp::socket& sock) {
         integer D(disc);
         integer L = root(-D, 4);
         PrintInfo("Discriminant = " + to_string(D.impl));
-        form f = DeserializeForm(D, initial_form_s, sizeof(initial_form_s));
+        form f = DeserializeForm(D, initial_form_s, initial_form_size);
         // Tell client that I'm ready to get the challenges.
         boost::asio::write(sock, boost::asio::buffer("OK", 2));
 
@@ -234,7 +234,7 @@ void SessionTwoWeso(tcp::socket& sock) {
         integer D(disc);
         integer L = root(-D, 4);
         PrintInfo("Discriminant = " + to_string(D.impl));
-        form f = DeserializeForm(D, initial_form_s, sizeof(initial_form_s));
+        form f = DeserializeForm(D, initial_form_s, initial_form_size);
 
         // Tell client that I'm ready to get the challenges.
         boost::asio::write(sock, boost::asio::buffer("OK", 2));
@@ -329,8 +329,6 @@ int main(int argc, char* argv[]) try {
 
     tcp::socket s(io_context);
     boost::asio::connect(s, endpoints);
-    fast_algorithm = false;
-    two_weso = false;
     boost::system::error_code error;
     char prover_type_buf[5];
     boost::asio::read(s, boost::asio::buffer(prover_type_buf, 1), error);
@@ -339,11 +337,9 @@ int main(int argc, char* argv[]) try {
         SessionOneWeso(s);
     }
     if (prover_type_buf[0] == 'N') {
-        fast_algorithm = true;
         SessionFastAlgorithm(s);
     }
     if (prover_type_buf[0] == 'T') {
-        two_weso = true;
         SessionTwoWeso(s);
     }
     return 0;
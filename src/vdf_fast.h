@@ -1152,7 +1152,9 @@ uint64 repeated_square_fast_single_thread(square_state_type &square_state, form&
 //if this returns ~0, the discriminant was invalid and the inputs are unchanged
 uint64 repeated_square_fast(square_state_type &square_state,form& f, const integer& D, const integer& L, uint64 base, uint64 iterations, INUDUPLListener *nuduplListener) {
 
-    if (enable_threads) {
+    // The master and slave threads spin on each other, so they need a core each
+    static const bool multi_core = std::thread::hardware_concurrency() > 1;
+    if (enable_threads && multi_core) {
         return repeated_square_fast_multithread(square_state, f, D, L, base, iterations, nuduplListener);
     } else {
         return repeated_square_fast_single_thread(square_state, f, D, L, base, iterations, nuduplListener);
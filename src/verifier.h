@@ -41,9 +41,33 @@ void VerifyWesolowskiProof(integer &D, form x, form y, form proof, uint64_t iter
     }
 }
 
+// Check Wesolowski proofs at once with a random linear combination: given the random weights e_i,
+// the product of (proof_i^B_i * x_i^r_i * y_i^-1)^e_i is the identity if all of them are valid.
+// A false result does not tell which proofs are invalid.
+bool VerifyWesolowskiBatch(integer &D, const std::vector<form>& xs, const std::vector<form>& ys, const std::vector<form>& proofs,
+                           const std::vector<uint64_t>& iters, const std::vector<integer>& weights)
+{
+    PulmarkReducer reducer;
+    integer L = root(-D, 4);
+    std::vector<form> bases;
+    std::vector<integer> exponents;
+    for (size_t i = 0; i < xs.size(); i++) {
+        form x = xs[i], y = ys[i];
+        integer B = GetB(D, x, y);
+        integer r = FastPow(2, iters[i], B);
+        bases.push_back(proofs[i]);
+        exponents.push_back(B * weights[i]);
+        bases.push_back(x);
+        exponents.push_back(r * weights[i]);
+        bases.push_back(y.inverse());
+        exponents.push_back(weights[i]);
+    }
+    return MultiPowFormNucomp(bases, exponents, D, L, reducer) == form::identity(D);
+}
+
 bool CheckProofOfTimeNWesolowski(integer D, const uint8_t* x_s, const uint8_t* proof_blob, int32_t proof_blob_len, uint64_t iterations, uint64 disc_size_bits, int32_t depth)
 {
-    int form_size = BQFC_FORM_SIZE;
+    int form_size = bqfc_get_compr_size(D.num_bits());
     int segment_len = 8 + B_bytes + form_size;
     // Enforce all invariants and bounds before the loop        
     if (form_size <= 0) return false;
@@ -82,7 +106,7 @@ bool CheckProofOfTimeNWesolowski(integer D, const uint8_t* x_s, const uint8_t* p
 }
 
 bool CheckProofOfTimeNWesolowskiCommon(integer& D, form& x, const uint8_t* proof_blob, int32_t proof_blob_len, uint64_t& iterations, int last_segment, bool skip_check = false) {
-    int form_size = BQFC_FORM_SIZE;
+    int form_size = bqfc_get_compr_size(D.num_bits());
     int segment_len = 8 + B_bytes + form_size;
     if (proof_blob == nullptr) return false;
     if (last_segment < 0 || proof_blob_len < 0) return false;
@@ -118,7 +142,7 @@ bool CheckProofOfTimeNWesolowskiCommon(integer& D, form& x, const uint8_t* proof
 }
 
 std::pair<bool, std::vector<uint8_t>> CheckProofOfTimeNWesolowskiWithB(integer D, integer B, const uint8_t* x_s, const uint8_t* proof_blob, int32_t proof_blob_len, uint64_t iterations, int32_t depth) {
-    int form_size = BQFC_FORM_SIZE;
+    int form_size = bqfc_get_compr_size(D.num_bits());
     int segment_len = 8 + B_bytes + form_size;
     if (x_s == nullptr || proof_blob == nullptr) return {false, {}};
     form x = DeserializeForm(D, x_s, form_size);
@@ -142,7 +166,7 @@ std::pair<bool, std::vector<uint8_t>> CheckProofOfTimeNWesolowskiWithB(integer D
 }
 
 integer GetBFromProof(integer D, const uint8_t* x_s, const uint8_t* proof_blob, int32_t proof_blob_len, uint64_t iterations, int32_t depth) {
-    int form_size = BQFC_FORM_SIZE;
+    int form_size = bqfc_get_compr_size(D.num_bits());
     int segment_len = 8 + B_bytes + form_size;
     if (x_s == nullptr || proof_blob == nullptr) throw std::runtime_error("Invalid proof.");
     form x = DeserializeForm(D, x_s, form_size);
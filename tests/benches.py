@@ -17,10 +17,6 @@ from chiavdf import (
 
 
 def bench_prove_and_verify():
-    # hard coded class group's element size in bqfc.h
-    # if BQFC_MAX_D_BITS is changed, this value must be changed accordingly
-    form_size = 388
-
     discriminant_challenge = secrets.token_bytes(10)
 
     discriminant_sizes = [4096]
@@ -31,6 +27,8 @@ def bench_prove_and_verify():
     table_alpha = []
 
     for d in discriminant_sizes:
+        # class group's element size for the discriminant, bqfc_get_compr_size in bqfc.c
+        form_size = (d + 31) // 32 * 3 + 4
         a_size = 128
         if d == 2048:
             a_size = 80
@@ -72,9 +72,8 @@ def test_prove_n_weso_and_verify():
         f"discriminant_challenge {discriminant_challenge.hex()} discriminant {discriminant}"
     )
 
-    # hard coded class group's element size in bqfc.h
-    # if BQFC_MAX_D_BITS is changed, this value must be changed accordingly
-    form_size = 388
+    # class group's element size for the discriminant, bqfc_get_compr_size in bqfc.c
+    form_size = (discriminant_size + 31) // 32 * 3 + 4
     initial_el = b"\x08" + (b"\x00" * (form_size - 1))
 
     for iters in [1_000, 5_000_000, 10_000_000]: